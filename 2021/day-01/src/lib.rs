@@ -0,0 +1,69 @@
+use aoc_common::{parse, ParseError, Solution};
+use itertools::Itertools;
+
+fn parse_report(report: &str) -> Result<Vec<u32>, ParseError> {
+    report
+        .lines()
+        .map(|line| parse::value(line, "a depth measurement"))
+        .collect()
+}
+
+fn count_larger_measurements(report: &[u32]) -> usize {
+    Itertools::tuple_windows(report.iter()).fold(0, |count, (x, y)| count + (x < y) as usize)
+}
+
+fn count_sliding_sum_increases(report: &[u32]) -> usize {
+    Itertools::tuple_windows(Itertools::tuple_windows(report.iter()).map(|(a, b, c)| a + b + c))
+        .fold(0, |count, (sum, next_sum)| {
+            count + (sum < next_sum) as usize
+        })
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+    let report = parse_report(INPUT).unwrap();
+
+    // Part 1.
+    let larger_measurements = count_larger_measurements(&report);
+    println!("Test part 1 larger measurements: {larger_measurements}");
+    assert_eq!(larger_measurements, 7);
+
+    // Part 2.
+    let sum_increases = count_sliding_sum_increases(&report);
+    println!("Test part 2 larger window sums: {sum_increases}");
+    assert_eq!(sum_increases, 5);
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse_report(input)
+    }
+
+    fn part1(report: &Vec<u32>) -> String {
+        count_larger_measurements(report).to_string()
+    }
+
+    fn part2(report: &Vec<u32>) -> String {
+        count_sliding_sum_increases(report).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day01);
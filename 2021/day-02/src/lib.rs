@@ -0,0 +1,105 @@
+use aoc_common::{parse, ParseError, Solution};
+
+pub enum Command {
+    Down(i32),
+    Up(i32),
+    Forward(i32),
+}
+
+impl Command {
+    /// How this command moves the submarine, read as a plain direction.
+    fn delta(&self) -> (i32, i32) {
+        match *self {
+            Command::Forward(amount) => (amount, 0),
+            Command::Down(amount) => (0, amount),
+            Command::Up(amount) => (0, -amount),
+        }
+    }
+}
+
+fn parse_command(line: &str) -> Result<Command, ParseError> {
+    let (dir, amount) = parse::split_once(line, " ")?;
+    let amount = parse::value(amount, "an amount")?;
+
+    Ok(match dir {
+        "down" => Command::Down(amount),
+        "up" => Command::Up(amount),
+        "forward" => Command::Forward(amount),
+        d => return Err(ParseError::new(d, "a direction")),
+    })
+}
+
+fn parse_commands(directions: &str) -> Result<Vec<Command>, ParseError> {
+    directions.lines().map(parse_command).collect()
+}
+
+fn final_position(commands: &[Command]) -> (i32, i32) {
+    commands
+        .iter()
+        .map(Command::delta)
+        .fold((0, 0), |(pos_x, pos_y), (delta_x, delta_y)| {
+            (pos_x + delta_x, pos_y + delta_y)
+        })
+}
+
+fn final_position_with_aim(commands: &[Command]) -> (i32, i32, i32) {
+    commands
+        .iter()
+        .fold((0, 0, 0), |(pos_x, pos_y, aim), cmd| match *cmd {
+            Command::Down(amount) => (pos_x, pos_y, aim + amount),
+            Command::Up(amount) => (pos_x, pos_y, aim - amount),
+            Command::Forward(amount) => (pos_x + amount, pos_y + aim * amount, aim),
+        })
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    let commands = parse_commands(INPUT).unwrap();
+
+    // Part 1.
+    let (final_pos_x, final_pos_y) = final_position(&commands);
+    let mul = final_pos_x * final_pos_y;
+    println!("Final position: {final_pos_x}, {final_pos_y}");
+    println!("Multiply: {mul}");
+    assert_eq!(mul, 150);
+
+    // Part 2.
+    let (final_pos_x, final_pos_y, final_aim) = final_position_with_aim(&commands);
+    let mul = final_pos_x * final_pos_y;
+    println!("Final position: {final_pos_x}, {final_pos_y}");
+    println!("Final aim: {final_aim}");
+    println!("Multiply: {mul}");
+    assert_eq!(mul, 900);
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 2;
+
+    type Parsed<'a> = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+        parse_commands(input)
+    }
+
+    fn part1(commands: &Vec<Command>) -> String {
+        let (final_pos_x, final_pos_y) = final_position(commands);
+        (final_pos_x * final_pos_y).to_string()
+    }
+
+    fn part2(commands: &Vec<Command>) -> String {
+        let (final_pos_x, final_pos_y, _final_aim) = final_position_with_aim(commands);
+        (final_pos_x * final_pos_y).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day02);
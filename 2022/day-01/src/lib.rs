@@ -0,0 +1,101 @@
+use aoc_common::{parse, ParseError, Solution};
+
+/// Each elf's inventory: the calories of each item it carries.
+fn parse_inventories(contents: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut inventories = vec![vec![]];
+
+    for line in contents.lines() {
+        if line.is_empty() {
+            inventories.push(vec![]);
+            continue;
+        }
+
+        let itemcals = parse::value(line, "a calorie count")?;
+        inventories.last_mut().unwrap().push(itemcals);
+    }
+
+    Ok(inventories)
+}
+
+fn part1(inventories: &[Vec<usize>]) -> usize {
+    let mut maxcals = 0;
+
+    for inventory in inventories {
+        let mut calories = 0;
+        for itemcals in inventory {
+            calories += itemcals;
+        }
+        maxcals = maxcals.max(calories);
+    }
+
+    maxcals
+}
+
+fn part1_take2(inventories: &[Vec<usize>]) -> usize {
+    inventories
+        .iter()
+        .map(|inventory| inventory.iter().sum())
+        .max()
+        .unwrap()
+}
+
+#[derive(Copy, Clone)]
+struct TopThree {
+    lo: usize,
+    mid: usize,
+    hi: usize,
+}
+
+impl TopThree {
+    fn new() -> Self {
+        TopThree { lo: 0, mid: 0, hi: 0 }
+    }
+
+    fn insert(&mut self, n: usize) {
+        if n >= self.hi {
+            self.lo = self.mid;
+            self.mid = self.hi;
+            self.hi = n;
+        } else if n >= self.mid {
+            self.lo = self.mid;
+            self.mid = n;
+        } else if n >= self.lo {
+            self.lo = n;
+        }
+    }
+}
+
+fn part2(inventories: &[Vec<usize>]) -> usize {
+    let mut top_three = TopThree::new();
+
+    for inventory in inventories {
+        top_three.insert(inventory.iter().sum());
+    }
+
+    top_three.hi + top_three.mid + top_three.lo
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = Vec<Vec<usize>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+        parse_inventories(input)
+    }
+
+    fn part1(inventories: &Vec<Vec<usize>>) -> String {
+        let maxcals = part1(inventories);
+        assert_eq!(maxcals, part1_take2(inventories));
+        maxcals.to_string()
+    }
+
+    fn part2(inventories: &Vec<Vec<usize>>) -> String {
+        part2(inventories).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day01);
@@ -0,0 +1,132 @@
+use aoc_common::{parse, ParseError, Solution};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Play {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+fn shape_score(play: Play) -> u64 {
+    match play {
+        Play::Rock => 1,
+        Play::Paper => 2,
+        Play::Scissors => 3,
+    }
+}
+
+/// The second column of the strategy guide, whose meaning differs between
+/// the parts.
+#[derive(Copy, Clone)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+fn opponent_play(s: &str) -> Result<Play, ParseError> {
+    Ok(match s {
+        "A" => Play::Rock,
+        "B" => Play::Paper,
+        "C" => Play::Scissors,
+        _ => return Err(ParseError::new(s, "an opponent play")),
+    })
+}
+
+fn second_column(s: &str) -> Result<Column, ParseError> {
+    Ok(match s {
+        "X" => Column::X,
+        "Y" => Column::Y,
+        "Z" => Column::Z,
+        _ => return Err(ParseError::new(s, "X, Y, or Z")),
+    })
+}
+
+fn your_play(c: Column) -> Play {
+    match c {
+        Column::X => Play::Rock,
+        Column::Y => Play::Paper,
+        Column::Z => Play::Scissors,
+    }
+}
+
+type Round = (Play, Column);
+
+fn parse_guide(contents: &str) -> Result<Vec<Round>, ParseError> {
+    contents
+        .lines()
+        .map(|line| {
+            let (op, column) = parse::split_once(line, " ")?;
+            Ok((opponent_play(op)?, second_column(column)?))
+        })
+        .collect()
+}
+
+fn outcome_score(op: Play, yp: Play) -> u64 {
+    if op == yp {
+        3
+    } else if ((op as u8 + 1) % 3) == yp as u8 {
+        6
+    } else {
+        0
+    }
+}
+
+fn round_score(op: Play, yp: Play) -> u64 {
+    shape_score(yp) + outcome_score(op, yp)
+}
+
+fn part1(rounds: &[Round]) -> u64 {
+    rounds
+        .iter()
+        .map(|&(op, column)| round_score(op, your_play(column)))
+        .sum()
+}
+
+fn to_play(p: u8) -> Play {
+    match p {
+        0 => Play::Rock,
+        1 => Play::Paper,
+        2 => Play::Scissors,
+        _ => panic!("unexpected opponent play value: {}", p),
+    }
+}
+
+fn part2(rounds: &[Round]) -> u64 {
+    rounds
+        .iter()
+        .map(|&(op, column)| {
+            let opn = op as u8;
+            let yp = to_play(match column {
+                Column::X => (opn + 2) % 3,
+                Column::Y => opn,
+                Column::Z => (opn + 1) % 3,
+            });
+
+            round_score(op, yp)
+        })
+        .sum()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 2;
+
+    type Parsed<'a> = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Vec<Round>, ParseError> {
+        parse_guide(input)
+    }
+
+    fn part1(rounds: &Vec<Round>) -> String {
+        part1(rounds).to_string()
+    }
+
+    fn part2(rounds: &Vec<Round>) -> String {
+        part2(rounds).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day02);
@@ -0,0 +1,103 @@
+use aoc_common::{ParseError, Solution};
+use itertools::Itertools;
+
+fn to_priority(c: char) -> u64 {
+    if c.is_ascii_lowercase() {
+        (c as u64 - 'a' as u64) + 1
+    } else if c.is_ascii_uppercase() {
+        (c as u64 - 'A' as u64) + 27
+    } else {
+        panic!("bad char: {}", c);
+    }
+}
+
+/// Each rucksack's items, which must be letters split evenly between its two
+/// compartments.  The rucksacks come in groups of three.
+fn parse_rucksacks(contents: &str) -> Result<Vec<&str>, ParseError> {
+    let rucksacks = contents
+        .lines()
+        .map(|line| {
+            if let Some(i) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::new(&line[i..i + 1], "an item letter"));
+            }
+            if line.len() % 2 != 0 {
+                return Err(ParseError::new(line, "an even number of items"));
+            }
+            Ok(line)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if rucksacks.len() % 3 != 0 {
+        return Err(ParseError::after(
+            contents.trim_end(),
+            "rucksacks in groups of three",
+        ));
+    }
+    Ok(rucksacks)
+}
+
+fn priorities(s: &str) -> u64 {
+    s.chars().fold(0u64, |acc, c| acc | (1 << to_priority(c)))
+}
+
+fn part1(rucksacks: &[&str]) -> u64 {
+    rucksacks
+        .iter()
+        .map(|line| {
+            let (first, second) = line.split_at(line.len() / 2);
+
+            let first_priorities = priorities(first);
+            let second_priorities = priorities(second);
+
+            let shared = first_priorities & second_priorities;
+            shared.trailing_zeros() as u64
+        })
+        .sum()
+}
+
+fn part2(rucksacks: &[&str]) -> u64 {
+    rucksacks
+        .iter()
+        .chunks(3)
+        .into_iter()
+        .map(|mut sacks| {
+            let first = priorities(sacks.next().unwrap());
+            let second = priorities(sacks.next().unwrap());
+            let third = priorities(sacks.next().unwrap());
+
+            (first & second & third).trailing_zeros() as u64
+        })
+        .sum()
+}
+
+#[test]
+fn incomplete_group() {
+    let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n";
+    let error = parse_rucksacks(input).unwrap_err().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 33: expected rucksacks in groups of three, found \"\""
+    );
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 3;
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        parse_rucksacks(input)
+    }
+
+    fn part1(rucksacks: &Vec<&str>) -> String {
+        part1(rucksacks).to_string()
+    }
+
+    fn part2(rucksacks: &Vec<&str>) -> String {
+        part2(rucksacks).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day03);
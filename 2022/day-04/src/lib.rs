@@ -0,0 +1,72 @@
+use aoc_common::{parse, ParseError, Solution};
+
+type Range = (u32, u32);
+
+fn parse_range(s: &str) -> Result<Range, ParseError> {
+    let (start, end) = parse::split_once(s, "-")?;
+    Ok((
+        parse::value(start, "range start")?,
+        parse::value(end, "range end")?,
+    ))
+}
+
+fn parse_pairs(contents: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    contents
+        .lines()
+        .map(|line| {
+            let (first, second) = parse::split_once(line, ",")?;
+            Ok((parse_range(first)?, parse_range(second)?))
+        })
+        .collect()
+}
+
+fn part1(pairs: &[(Range, Range)]) -> u32 {
+    pairs
+        .iter()
+        .map(|&((first_start, first_end), (second_start, second_end))| {
+            if (first_start <= second_start && second_end <= first_end)
+                || (second_start <= first_start && first_end <= second_end)
+            {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+fn part2(pairs: &[(Range, Range)]) -> u32 {
+    pairs
+        .iter()
+        .map(|&((first_start, first_end), (second_start, second_end))| {
+            if first_start <= second_end && second_start <= first_end {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = Vec<(Range, Range)>;
+
+    fn parse(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn part1(pairs: &Vec<(Range, Range)>) -> String {
+        part1(pairs).to_string()
+    }
+
+    fn part2(pairs: &Vec<(Range, Range)>) -> String {
+        part2(pairs).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day04);
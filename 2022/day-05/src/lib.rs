@@ -0,0 +1,243 @@
+use aoc_common::{parse, ParseError, Solution};
+
+/// Parse one row of crates in the drawing of the piles, e.g. "[Z] [M] [P]",
+/// returning the crate (if any) atop each pile in the row.  Rows may stop
+/// short after the last crate in them.
+fn parse_crate_row(line: &str, pile_count: usize) -> Result<Vec<Option<char>>, ParseError> {
+    let chars = line.char_indices().collect::<Vec<_>>();
+    let text = |from: usize, len: usize| {
+        let start = chars[from].0;
+        let end = chars.get(from + len).map_or(line.len(), |&(i, _)| i);
+        &line[start..end]
+    };
+
+    let mut crates = vec![];
+    for (pile, cell) in chars.chunks(4).enumerate() {
+        let from = 4 * pile;
+        if pile == pile_count {
+            return Err(ParseError::new(
+                text(from, cell.len()),
+                format!("at most {pile_count} piles"),
+            ));
+        }
+
+        // "[X] ", or all spaces for no crate.
+        let cell_chars = cell.iter().map(|&(_, c)| c).collect::<Vec<_>>();
+        let crate_char = match cell_chars[..] {
+            ['[', c, ']'] | ['[', c, ']', ' '] if c != ' ' => Some(c),
+            _ if cell_chars.iter().all(|&c| c == ' ') => None,
+            _ => {
+                return Err(ParseError::new(
+                    text(from, cell.len()),
+                    "a crate like \"[X]\"",
+                ))
+            }
+        };
+        crates.push(crate_char);
+    }
+
+    Ok(crates)
+}
+
+/// Parse the drawing of the piles of crates, e.g.
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+///
+/// into each pile's crates, from bottom to top.
+fn parse_piles(drawing: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut lines = drawing.lines().rev();
+    let labels = parse::next(&mut lines, drawing, "pile numbers")?;
+
+    // The piles are numbered 1, 2, 3 and so on, left to right.
+    let mut pile_count = 0;
+    for label in labels.split_whitespace() {
+        pile_count += 1;
+        if parse::value::<usize>(label, "a pile number")? != pile_count {
+            return Err(ParseError::new(label, format!("pile number {pile_count}")));
+        }
+    }
+    if pile_count == 0 {
+        return Err(ParseError::new(labels, "pile numbers"));
+    }
+
+    let mut piles = vec![vec![]; pile_count];
+    for (height, line) in lines.enumerate() {
+        for (pile, crate_char) in parse_crate_row(line, pile_count)?.into_iter().enumerate() {
+            let Some(crate_char) = crate_char else {
+                continue;
+            };
+            if piles[pile].len() != height {
+                let at = line
+                    .char_indices()
+                    .nth(4 * pile)
+                    .map_or(line, |(i, _)| &line[i..]);
+                return Err(ParseError::new(at, "a crate resting on the one below"));
+            }
+            piles[pile].push(crate_char);
+        }
+    }
+
+    Ok(piles)
+}
+
+#[derive(Copy, Clone)]
+pub struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+fn parse_moves(content: &str, pile_count: usize) -> Result<Vec<Move>, ParseError> {
+    let mut moves = vec![];
+
+    let pile = |text: &str| {
+        let pile = parse::value(text, "a pile number")?;
+        if (1..=pile_count).contains(&pile) {
+            Ok(pile)
+        } else {
+            Err(ParseError::new(
+                text,
+                format!("a pile number from 1 to {pile_count}"),
+            ))
+        }
+    };
+
+    for line in content.lines() {
+        let mut splits = line.split(' ');
+
+        let move_token = parse::next(&mut splits, line, "\"move\"")?;
+        if move_token != "move" {
+            return Err(ParseError::new(move_token, "\"move\""));
+        }
+
+        let mut token = |expected| parse::next(&mut splits, line, expected);
+
+        let count = parse::value(token("count")?, "count")?;
+
+        let from_token = token("\"from\"")?;
+        if from_token != "from" {
+            return Err(ParseError::new(from_token, "\"from\""));
+        }
+        let from = pile(token("from")?)?;
+
+        let to_token = token("\"to\"")?;
+        if to_token != "to" {
+            return Err(ParseError::new(to_token, "\"to\""));
+        }
+        let to = pile(token("to")?)?;
+
+        if let Some(extra) = splits.next() {
+            return Err(ParseError::new(extra, "end of line"));
+        }
+
+        moves.push(Move { count, from, to });
+    }
+
+    Ok(moves)
+}
+
+enum Part {
+    Part1,
+    Part2,
+}
+
+/// The crates atop the piles after the moves, skipping any empty piles, or
+/// a description of the first move taking more crates than its pile holds.
+fn part(mut piles: Vec<Vec<char>>, moves: &[Move], part: Part) -> String {
+    for (i, &Move { count, from, to }) in moves.iter().enumerate() {
+        let held = piles[from - 1].len();
+        if count > held {
+            return format!(
+                "move {} takes {count} crates from pile {from}, which holds {held}",
+                i + 1
+            );
+        }
+
+        match part {
+            Part::Part1 => {
+                for _ in 1..=count {
+                    let removed = piles[from - 1].pop().expect("from pile");
+                    piles[to - 1].push(removed);
+                }
+            }
+            Part::Part2 => {
+                let from = &mut piles[from - 1];
+                let removed = from.split_off(from.len() - count);
+                piles[to - 1].extend(removed);
+            }
+        }
+    }
+
+    piles.iter().filter_map(|pile| pile.last()).collect()
+}
+
+#[test]
+fn parses_drawings() {
+    // Ragged rows, and more than nine piles.
+    let drawing = [
+        "                                    [K]",
+        "[A]                 [F]             [J]",
+        "[B] [C] [D] [E] [G] [H] [L] [M] [N] [O]",
+        " 1   2   3   4   5   6   7   8   9   10 ",
+    ]
+    .join("\n");
+    let piles = parse_piles(&drawing).unwrap();
+    assert_eq!(piles.len(), 10);
+    assert_eq!(piles[0], ['B', 'A']);
+    assert_eq!(piles[5], ['H', 'F']);
+    assert_eq!(piles[9], ['O', 'J', 'K']);
+
+    let input = format!("{drawing}\n\nmove 2 from 10 to 1\n");
+    let (piles, moves) = Day05::parse(&input).unwrap();
+    assert_eq!(part(piles.clone(), &moves, Part::Part1), "JCDEGFLMNO");
+    assert_eq!(part(piles, &moves, Part::Part2), "KCDEGFLMNO");
+
+    // A floating crate, a crate past the last pile, misnumbered piles, and
+    // moves to or from piles that aren't there.
+    assert!(parse_piles("    [A]\n[B]\n 1   2 ").is_err());
+    assert!(parse_piles("[A]\n[B] [C]\n 1 ").is_err());
+    assert!(parse_piles("[A] [B]\n 1   3 ").is_err());
+    assert!(parse_piles("[A] [B\n 1   2 ").is_err());
+    assert!(Day05::parse("[A]\n 1 \n\nmove 1 from 1 to 2").is_err());
+    assert!(Day05::parse("[A]\n 1 \n\nmove 1 from 0 to 1").is_err());
+
+    // Emptying a pile, and taking more crates than a pile holds.
+    let (piles, moves) = Day05::parse("[A]\n 1   2 \n\nmove 1 from 1 to 2").unwrap();
+    assert_eq!(part(piles.clone(), &moves, Part::Part1), "A");
+    let (piles, moves) = Day05::parse("[A]\n 1   2 \n\nmove 2 from 1 to 2").unwrap();
+    assert_eq!(
+        part(piles, &moves, Part::Part2),
+        "move 1 takes 2 crates from pile 1, which holds 1"
+    );
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 5;
+
+    type Parsed<'a> = (Vec<Vec<char>>, Vec<Move>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let (drawing, moves) = parse::split_once(input, "\n\n")?;
+        let piles = parse_piles(drawing)?;
+        let moves = parse_moves(moves, piles.len())?;
+        Ok((piles, moves))
+    }
+
+    fn part1((piles, moves): &Self::Parsed<'_>) -> String {
+        part(piles.clone(), moves, Part::Part1)
+    }
+
+    fn part2((piles, moves): &Self::Parsed<'_>) -> String {
+        part(piles.clone(), moves, Part::Part2)
+    }
+}
+
+aoc_common::example_tests!(Day05);
@@ -0,0 +1,105 @@
+use aoc_common::{ParseError, Solution};
+
+static START_OF_PACKET_LEN: usize = 4;
+static START_OF_MESSAGE_LEN: usize = 14;
+
+fn to_bit(c: char) -> u32 {
+    let n = c as u8 - b'a';
+    assert!(n < 26, "must fit in u32");
+
+    1u32 << n
+}
+
+/// The datastream, which must be lowercase letters, at least enough for a
+/// start-of-packet marker.
+fn parse_datastream(contents: &str) -> Result<&str, ParseError> {
+    let contents = contents.trim_end();
+    if let Some(i) = contents.find(|c: char| !c.is_ascii_lowercase()) {
+        return Err(ParseError::new(&contents[i..i + 1], "a lowercase letter"));
+    }
+    if contents.len() < START_OF_PACKET_LEN {
+        return Err(ParseError::after(
+            contents,
+            format!("at least {START_OF_PACKET_LEN} letters"),
+        ));
+    }
+    Ok(contents)
+}
+
+/// How many characters are read up through the end of the first marker
+/// `component_len` long, if there is one.
+fn start_of_component(contents: &str, component_len: usize) -> Option<usize> {
+    let mut buffer = vec![0; component_len];
+    let mut start = 0;
+    let mut len = 0;
+
+    let mut bitset = 0u32;
+
+    for (index, c) in contents.chars().enumerate() {
+        let b = to_bit(c);
+
+        if bitset & b != 0 {
+            loop {
+                let s = buffer[start];
+                bitset &= !s;
+                start = (start + 1) % component_len;
+                len -= 1;
+
+                if s == b {
+                    break;
+                }
+            }
+        }
+
+        buffer[(start + len) % component_len] = b;
+        bitset |= b;
+        len += 1;
+
+        if len == component_len {
+            return Some(index + 1);
+        }
+    }
+
+    None
+}
+
+fn describe_start(start: Option<usize>) -> String {
+    match start {
+        Some(start) => start.to_string(),
+        None => "no marker".to_string(),
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 6;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        parse_datastream(input)
+    }
+
+    fn part1(contents: &&str) -> String {
+        describe_start(start_of_component(contents, START_OF_PACKET_LEN))
+    }
+
+    fn part2(contents: &&str) -> String {
+        describe_start(start_of_component(contents, START_OF_MESSAGE_LEN))
+    }
+}
+
+aoc_common::example_tests!(Day06);
+
+#[test]
+fn no_marker() {
+    assert!(Day06::parse("").is_err());
+    assert!(Day06::parse("abc\n").is_err());
+
+    let contents = Day06::parse("abcabcdaaaa").unwrap();
+    assert_eq!(start_of_component(contents, START_OF_PACKET_LEN), Some(7));
+    assert_eq!(start_of_component(contents, START_OF_MESSAGE_LEN), None);
+    assert_eq!(Day06::part2(&contents), "no marker");
+}
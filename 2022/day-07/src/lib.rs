@@ -0,0 +1,247 @@
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+use std::iter::Peekable;
+use std::str::Lines;
+
+// File sizes are only ever summed into directory sizes while parsing, but
+// they're kept around so that `{:?}` output shows the full tree.
+#[allow(dead_code)]
+#[derive(Debug)]
+struct File {
+    size: usize,
+}
+
+type DirectoryContents = HashMap<String, Entry>;
+
+#[derive(Debug)]
+pub struct Directory {
+    contents: DirectoryContents,
+    size: usize,
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+enum Entry {
+    Dir(Directory),
+    File(File),
+}
+
+fn parse_directory_listing(
+    lines: &mut Peekable<Lines>,
+) -> Result<(DirectoryContents, HashSet<String>, usize), ParseError> {
+    let mut dir_contents = DirectoryContents::new();
+    let mut unconsumed_subdirs = HashSet::<String>::new();
+    let mut total_files_size = 0;
+
+    // Parse directory contents.
+    loop {
+        let peeked_line = match lines.peek() {
+            None => break,
+            Some(peeked_line) => *peeked_line,
+        };
+
+        // Peek at next line without consuming it in case it's not directory contents.
+        let mut toks = peeked_line.split(' ');
+
+        let first_tok = if let Some(tok) = toks.next() {
+            tok
+        } else {
+            // Consume the empty line.
+            lines.next();
+            break;
+        };
+
+        if first_tok == "$" {
+            // Command.  Continue to in-directory command parsing without
+            // consuming the line.
+            break;
+        }
+
+        // Definitely directory contents, so consume the line.
+        lines.next();
+
+        if first_tok == "dir" {
+            // Add it to directory contents, continue next contents.
+            let dirname = parse::next(&mut toks, peeked_line, "directory name")?;
+            let freshly_inserted = unconsumed_subdirs.insert(dirname.to_owned());
+            if !freshly_inserted {
+                return Err(ParseError::new(dirname, "a directory not already listed"));
+            }
+        } else {
+            let filesz = parse::value::<usize>(first_tok, "size")?;
+            total_files_size += filesz;
+            let filename = parse::next(&mut toks, peeked_line, "filename")?;
+            dir_contents.insert(filename.to_owned(), Entry::File(File { size: filesz }));
+        }
+    } // processing directory contents listing
+
+    Ok((dir_contents, unconsumed_subdirs, total_files_size))
+}
+
+/// Parse the commands run within a directory, starting after the command
+/// that entered it, `entered`.
+fn parse_directory(lines: &mut Peekable<Lines>, entered: &str) -> Result<Directory, ParseError> {
+    let mut dir_entries = None;
+    let mut contained_dirs_size = 0;
+    let mut total_files_size = 0;
+    let mut last_line = entered;
+
+    // Run commands within directory.
+    loop {
+        let peeked_line = match lines.peek() {
+            None => break,
+            Some(peeked_line) => *peeked_line,
+        };
+
+        lines.next();
+        last_line = peeked_line;
+        let mut toks = peeked_line.split(' ');
+
+        let first_tok = if let Some(tok) = toks.next() {
+            tok
+        } else {
+            // Consume an empty line.
+            break;
+        };
+
+        if first_tok != "$" {
+            return Err(ParseError::new(first_tok, "a command"));
+        }
+
+        let command = parse::next(&mut toks, peeked_line, "command")?;
+        match command {
+            "ls" => {
+                let (contents, unconsumed_subdirs, files_size) = parse_directory_listing(lines)?;
+                dir_entries = Some((contents, unconsumed_subdirs));
+                total_files_size = files_size;
+                continue;
+            }
+            "cd" => {
+                let cd_name = parse::next(&mut toks, peeked_line, "cd <dirname>")?;
+                if cd_name == ".." {
+                    // Done with this directory.
+                    break;
+                }
+
+                let subdir = parse_directory(lines, peeked_line)?;
+                if let Some((ref mut dir_contents, ref mut unconsumed_subdirs)) = dir_entries {
+                    if !unconsumed_subdirs.contains(cd_name) {
+                        return Err(ParseError::new(cd_name, "a directory listed by ls"));
+                    }
+
+                    contained_dirs_size += subdir.size;
+                    unconsumed_subdirs.remove(cd_name);
+                    dir_contents.insert(cd_name.to_owned(), Entry::Dir(subdir));
+                }
+            }
+            cmd => {
+                return Err(ParseError::new(cmd, "ls or cd"));
+            }
+        }
+    } // Running commands within directory
+
+    // Left directory, return the directory.
+    let Some((dir_entries, unconsumed_subdirs)) = dir_entries else {
+        return Err(ParseError::after(last_line, "an ls of the directory"));
+    };
+    if !unconsumed_subdirs.is_empty() {
+        return Err(ParseError::after(
+            last_line,
+            "a cd into every listed subdirectory",
+        ));
+    }
+
+    Ok(Directory {
+        contents: dir_entries,
+        size: contained_dirs_size + total_files_size,
+    })
+}
+
+fn parse_input(contents: &str) -> Result<(String, Directory), ParseError> {
+    let mut lines = contents.lines().peekable();
+
+    let cd_line = parse::next(&mut lines, contents, "first line cd into top")?;
+    let cd_dir = parse::strip_prefix(cd_line, "$ cd ")?;
+
+    Ok((cd_dir.to_owned(), parse_directory(&mut lines, cd_line)?))
+}
+
+fn sum_sizes_up_to_100k(dir: &Directory) -> usize {
+    const MAX: usize = 100_000;
+
+    let mut sum_of_100k_sizes = 0;
+    if dir.size <= MAX {
+        sum_of_100k_sizes += dir.size;
+    }
+
+    for (_, entry) in dir.contents.iter() {
+        match entry {
+            Entry::File(_) => continue,
+            Entry::Dir(nested) => {
+                let nested_up_to_100k = sum_sizes_up_to_100k(nested);
+                sum_of_100k_sizes += nested_up_to_100k;
+            }
+        }
+    }
+
+    sum_of_100k_sizes
+}
+
+const TOTAL_DISK_SPACE: usize = 70_000_000;
+const UNUSED_SPACE_REQD: usize = 30_000_000;
+
+fn smallest_directory_at_least(dir: &Directory, size: usize) -> usize {
+    let mut best_size = usize::MAX;
+
+    macro_rules! update {
+        ($val:expr) => {
+            let v = $val;
+            if v >= size && v < best_size {
+                best_size = v;
+            }
+        };
+    }
+
+    update!(dir.size);
+
+    for (_, entry) in dir.contents.iter() {
+        match entry {
+            Entry::File(_) => continue,
+            Entry::Dir(nested) => {
+                update!(smallest_directory_at_least(nested, size));
+            }
+        }
+    }
+
+    best_size
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 7;
+
+    type Parsed<'a> = Directory;
+
+    fn parse(input: &str) -> Result<Directory, ParseError> {
+        let (_name, dir) = parse_input(input)?;
+        Ok(dir)
+    }
+
+    fn part1(dir: &Directory) -> String {
+        sum_sizes_up_to_100k(dir).to_string()
+    }
+
+    fn part2(dir: &Directory) -> String {
+        let unused_size = TOTAL_DISK_SPACE - dir.size;
+        let space_to_free = UNUSED_SPACE_REQD - unused_size;
+
+        match smallest_directory_at_least(dir, space_to_free) {
+            usize::MAX => panic!("no directory found of at least {space_to_free} size"),
+            smallest_dir_to_remove_size => smallest_dir_to_remove_size.to_string(),
+        }
+    }
+}
+
+aoc_common::example_tests!(Day07);
@@ -0,0 +1,257 @@
+use aoc_common::{ParseError, Solution};
+use aoc_grid::Grid;
+
+fn parse_trees(contents: &str) -> Result<Grid<i8>, ParseError> {
+    let trees = Grid::parse(contents, "a tree height digit", |c| {
+        c.to_digit(10).map(|d| d as i8 + 1)
+    })?;
+    if trees.width() == 0 || trees.height() == 0 {
+        return Err(ParseError::new(contents, "a grid of trees"));
+    }
+    Ok(trees)
+}
+
+fn count_visible_trees(trees: &mut Grid<i8>) -> usize {
+    let (row_count, col_count) = (trees.height(), trees.width());
+
+    // Mark any visible tree along a row or column, in positive or negative
+    // direction, as negative.
+    let row_iter = 0..row_count;
+    let rev_row_iter = row_iter.clone().rev();
+    let col_iter = 0..col_count;
+    let rev_col_iter = col_iter.clone().rev();
+
+    for i in row_iter.clone() {
+        let mut current_tree_height = 0;
+        for j in col_iter.clone() {
+            let tree = &mut trees[(j, i)];
+            if tree.abs() > current_tree_height {
+                current_tree_height = tree.abs();
+                *tree = -tree.abs();
+            }
+        }
+
+        let mut current_tree_height = -1;
+        for j in rev_col_iter.clone() {
+            let tree = &mut trees[(j, i)];
+            if tree.abs() > current_tree_height {
+                current_tree_height = tree.abs();
+                *tree = -tree.abs();
+            }
+        }
+    }
+
+    for j in col_iter.clone() {
+        let mut current_tree_height = -1;
+        for i in row_iter.clone() {
+            let tree = &mut trees[(j, i)];
+            if tree.abs() > current_tree_height {
+                current_tree_height = tree.abs();
+                *tree = -tree.abs();
+            }
+        }
+
+        let mut current_tree_height = -1;
+        for i in rev_row_iter.clone() {
+            let tree = &mut trees[(j, i)];
+            if tree.abs() > current_tree_height {
+                current_tree_height = tree.abs();
+                *tree = -tree.abs();
+            }
+        }
+    }
+
+    let visible_tree_count = trees.iter().filter(|v| **v < 0).count();
+
+    for tree in trees.iter_mut() {
+        *tree = tree.abs();
+    }
+
+    visible_tree_count
+}
+
+#[derive(Copy, Clone)]
+struct Partial {
+    lr: usize,
+    rl: usize,
+    tb: usize,
+    bt: usize,
+}
+
+impl Partial {
+    fn zero() -> Partial {
+        Partial {
+            lr: 0,
+            rl: 0,
+            tb: 0,
+            bt: 0,
+        }
+    }
+}
+
+fn find_best_scenic_score(trees: &Grid<i8>) -> usize {
+    let (row_count, col_count) = (trees.height(), trees.width());
+    let mut partials = Grid::new(col_count, row_count, Partial::zero());
+
+    for i in 0..row_count {
+        let elem = |j| trees[(j, i)];
+
+        let mut stack = vec![];
+        for j in 0..col_count {
+            let tree = elem(j);
+
+            loop {
+                match stack.last() {
+                    None => {
+                        stack.push(j);
+                        partials[(j, i)].lr = j;
+                        break;
+                    }
+                    Some(least) => {
+                        let least = *least;
+                        let low_tree = elem(least);
+                        if low_tree >= tree {
+                            if low_tree == tree {
+                                stack.pop();
+                            }
+                            stack.push(j);
+                            partials[(j, i)].lr = j - least;
+                            break;
+                        } else {
+                            stack.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut stack = vec![];
+        for j in (0..col_count).rev() {
+            let tree = elem(j);
+
+            loop {
+                match stack.last() {
+                    None => {
+                        stack.push(j);
+                        partials[(j, i)].rl = col_count - 1 - j;
+                        break;
+                    }
+                    Some(least) => {
+                        let least = *least;
+                        let low_tree = elem(least);
+                        if low_tree >= tree {
+                            if low_tree == tree {
+                                stack.pop();
+                            }
+                            stack.push(j);
+                            partials[(j, i)].rl = least - j;
+                            break;
+                        } else {
+                            stack.pop();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for j in 0..col_count {
+        let elem = |i: usize| trees[(j, i)];
+
+        let mut stack = vec![];
+        for i in 0..row_count {
+            let tree = elem(i);
+
+            loop {
+                match stack.last() {
+                    None => {
+                        stack.push(i);
+                        partials[(j, i)].tb = i;
+                        break;
+                    }
+                    Some(least) => {
+                        let least = *least;
+                        let low_tree = elem(least);
+                        if low_tree >= tree {
+                            if low_tree == tree {
+                                stack.pop();
+                            }
+                            stack.push(i);
+                            partials[(j, i)].tb = i - least;
+                            break;
+                        } else {
+                            stack.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut stack = vec![];
+        for i in (0..row_count).rev() {
+            let tree = elem(i);
+
+            loop {
+                match stack.last() {
+                    None => {
+                        stack.push(i);
+                        partials[(j, i)].bt = row_count - 1 - i;
+                        break;
+                    }
+                    Some(least) => {
+                        let least = *least;
+                        let low_tree = elem(least);
+                        if low_tree >= tree {
+                            if low_tree == tree {
+                                stack.pop();
+                            }
+                            stack.push(i);
+                            partials[(j, i)].bt = least - i;
+                            break;
+                        } else {
+                            stack.pop();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let scenic_score = |p: &Partial| p.lr * p.rl * p.bt * p.tb;
+
+    partials
+        .iter()
+        .map(scenic_score)
+        .max()
+        .expect("must have trees")
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 8;
+
+    type Parsed<'a> = Grid<i8>;
+
+    fn parse(input: &str) -> Result<Grid<i8>, ParseError> {
+        parse_trees(input)
+    }
+
+    fn part1(trees: &Grid<i8>) -> String {
+        let mut trees = trees.clone();
+        count_visible_trees(&mut trees).to_string()
+    }
+
+    fn part2(trees: &Grid<i8>) -> String {
+        find_best_scenic_score(trees).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day08);
+
+#[test]
+fn no_trees() {
+    assert!(parse_trees("").is_err());
+    assert!(parse_trees("\n\n").is_err());
+}
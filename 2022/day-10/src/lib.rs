@@ -0,0 +1,586 @@
+use aoc_common::ocr::{self, OcrError};
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::BTreeSet;
+
+/// What an opcode does to the registers once it finishes executing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Effect {
+    /// Nothing.
+    None,
+    /// Add the operand to the register with this index.
+    Add(usize),
+    /// Set the register with this index to the operand.
+    Set(usize),
+}
+
+impl Effect {
+    fn takes_operand(&self) -> bool {
+        !matches!(self, Effect::None)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Opcode {
+    pub name: &'static str,
+    /// How many cycles an instruction with this opcode takes to execute.
+    pub cycles: u32,
+    pub effect: Effect,
+}
+
+#[derive(Debug)]
+pub struct Register {
+    pub name: &'static str,
+    pub initial: i64,
+}
+
+/// The registers of a CPU and the opcodes it understands.
+#[derive(Debug)]
+pub struct InstructionSet {
+    pub registers: &'static [Register],
+    pub opcodes: &'static [Opcode],
+}
+
+/// The index of the puzzle CPU's only register.
+pub const X: usize = 0;
+
+/// The instruction set of the CPU in the puzzle.
+pub const PUZZLE_CPU: InstructionSet = InstructionSet {
+    registers: &[Register {
+        name: "x",
+        initial: 1,
+    }],
+    opcodes: &[
+        Opcode {
+            name: "noop",
+            cycles: 1,
+            effect: Effect::None,
+        },
+        Opcode {
+            name: "addx",
+            cycles: 2,
+            effect: Effect::Add(X),
+        },
+    ],
+};
+
+#[derive(Debug)]
+pub struct Instruction {
+    opcode: &'static Opcode,
+    operand: i64,
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.opcode.name)?;
+        if self.opcode.effect.takes_operand() {
+            write!(f, " {}", self.operand)?;
+        }
+        Ok(())
+    }
+}
+
+impl InstructionSet {
+    fn opcode(&self, name: &str) -> Option<&'static Opcode> {
+        self.opcodes.iter().find(|opcode| opcode.name == name)
+    }
+
+    pub fn parse_program(&self, s: &str) -> Result<Vec<Instruction>, ParseError> {
+        s.lines()
+            .map(|line| {
+                let mut it = line.split(' ');
+
+                let first = parse::next(&mut it, line, "instruction name")?;
+                let Some(opcode) = self.opcode(first) else {
+                    let names = self.opcodes.iter().map(|opcode| opcode.name);
+                    return Err(ParseError::new(
+                        first,
+                        names.collect::<Vec<_>>().join(" or "),
+                    ));
+                };
+
+                let operand = if opcode.effect.takes_operand() {
+                    let second = parse::next(&mut it, line, "operand")?;
+                    parse::value(second, "number")?
+                } else {
+                    0
+                };
+
+                if let Some(extra) = it.next() {
+                    return Err(ParseError::new(extra, "end of line"));
+                }
+
+                Ok(Instruction { opcode, operand })
+            })
+            .collect()
+    }
+}
+
+fn parse_instructions(s: &str) -> Result<Vec<Instruction>, ParseError> {
+    PUZZLE_CPU.parse_program(s)
+}
+
+/// Something watching a `Vm` run, cycle by cycle.
+pub trait Observer {
+    /// Observe the VM during its current cycle, before the instruction under
+    /// way has finished.
+    fn during(&mut self, vm: &Vm<'_>);
+}
+
+/// Why a `Vm` stopped running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// Every instruction has executed.
+    Halted,
+    /// This cycle, at which a breakpoint was set, is about to start.
+    Breakpoint(u64),
+}
+
+/// A CPU executing a program, one cycle at a time.
+pub struct Vm<'p> {
+    cpu: &'p InstructionSet,
+    program: &'p [Instruction],
+    registers: Vec<i64>,
+    /// The cycle about to happen or under way, starting with 1.
+    cycle: u64,
+    /// The index of the instruction executing.
+    pc: usize,
+    /// How many cycles the instruction at `pc` has executed for.
+    elapsed: u32,
+    breakpoints: BTreeSet<u64>,
+    /// The breakpoint last stopped at, not to be stopped at again.
+    stopped_at: Option<u64>,
+}
+
+impl<'p> Vm<'p> {
+    pub fn new(cpu: &'p InstructionSet, program: &'p [Instruction]) -> Vm<'p> {
+        Vm {
+            cpu,
+            program,
+            registers: cpu.registers.iter().map(|r| r.initial).collect(),
+            cycle: 1,
+            pc: 0,
+            elapsed: 0,
+            breakpoints: BTreeSet::new(),
+            stopped_at: None,
+        }
+    }
+
+    /// Stop running just before `cycle` starts.
+    pub fn break_at(&mut self, cycle: u64) {
+        self.breakpoints.insert(cycle);
+    }
+
+    pub fn cycle(&self) -> u64 {
+        self.cycle
+    }
+
+    pub fn registers(&self) -> &[i64] {
+        &self.registers
+    }
+
+    /// The instruction under way, if the program hasn't finished.
+    pub fn instruction(&self) -> Option<&'p Instruction> {
+        self.program.get(self.pc)
+    }
+
+    /// Run the program until it finishes or reaches a breakpoint, showing
+    /// each cycle to `observer`.  Running again resumes where it stopped.
+    pub fn run(&mut self, observer: &mut dyn Observer) -> Stop {
+        while let Some(instruction) = self.instruction() {
+            if self.elapsed == instruction.opcode.cycles {
+                self.execute(instruction);
+                continue;
+            }
+
+            if self.breakpoints.contains(&self.cycle) && self.stopped_at != Some(self.cycle) {
+                self.stopped_at = Some(self.cycle);
+                return Stop::Breakpoint(self.cycle);
+            }
+
+            observer.during(self);
+            self.cycle += 1;
+            self.elapsed += 1;
+        }
+
+        Stop::Halted
+    }
+
+    fn execute(&mut self, instruction: &Instruction) {
+        match instruction.opcode.effect {
+            Effect::None => {}
+            Effect::Add(r) => self.registers[r] += instruction.operand,
+            Effect::Set(r) => self.registers[r] = instruction.operand,
+        }
+        self.pc += 1;
+        self.elapsed = 0;
+    }
+}
+
+/// Records a line for each cycle with the instruction under way and the
+/// registers during it.
+#[derive(Default)]
+pub struct Trace {
+    pub lines: Vec<String>,
+}
+
+impl Observer for Trace {
+    fn during(&mut self, vm: &Vm<'_>) {
+        let instruction = vm.instruction().expect("instruction under way");
+        let registers = vm
+            .cpu
+            .registers
+            .iter()
+            .zip(vm.registers())
+            .map(|(register, value)| format!("{}={value}", register.name))
+            .collect::<Vec<_>>();
+        self.lines.push(format!(
+            "{:>4}: {:<10} {}/{}  {}",
+            vm.cycle(),
+            instruction.to_string(),
+            vm.elapsed + 1,
+            instruction.opcode.cycles,
+            registers.join(" ")
+        ));
+    }
+}
+
+/// Sums the signal strengths during the 20th cycle and every 40 cycles
+/// after that, through the 220th.
+#[derive(Default)]
+struct SignalStrengths {
+    sum: i64,
+}
+
+impl Observer for SignalStrengths {
+    fn during(&mut self, vm: &Vm<'_>) {
+        let cycle = vm.cycle();
+        if (20..=220).contains(&cycle) && (cycle - 20).is_multiple_of(40) {
+            self.sum += cycle as i64 * vm.registers()[X];
+        }
+    }
+}
+
+fn sum_strengths_every_twenty(insts: &[Instruction]) -> i64 {
+    let mut strengths = SignalStrengths::default();
+    Vm::new(&PUZZLE_CPU, insts).run(&mut strengths);
+    strengths.sum
+}
+
+const SCREEN_WIDTH: u32 = 40;
+const SCREEN_HEIGHT: u32 = 6;
+
+type Screen = Vec<Vec<char>>;
+
+/// Draws a pixel each cycle, lit if the sprite, three pixels wide and
+/// centered on X, covers it.
+struct Crt {
+    screen: Screen,
+}
+
+impl Observer for Crt {
+    fn during(&mut self, vm: &Vm<'_>) {
+        let row = (vm.cycle() - 1) / SCREEN_WIDTH as u64;
+        let col = (vm.cycle() - 1) % SCREEN_WIDTH as u64;
+
+        let sprite = vm.registers()[X];
+        if let Some(pixel) = self
+            .screen
+            .get_mut(row as usize)
+            .map(|line| &mut line[col as usize])
+        {
+            if (sprite - col as i64).abs() < 2 {
+                *pixel = '#';
+            }
+        }
+    }
+}
+
+fn draw_screen(insts: &[Instruction]) -> Screen {
+    let mut crt = Crt {
+        screen: vec![vec!['.'; SCREEN_WIDTH as usize]; SCREEN_HEIGHT as usize],
+    };
+    Vm::new(&PUZZLE_CPU, insts).run(&mut crt);
+    crt.screen
+}
+
+#[test]
+fn vm_runs_instruction_sets() {
+    let program = parse_instructions("noop\naddx 3\naddx -5").unwrap();
+    let mut trace = Trace::default();
+    assert_eq!(Vm::new(&PUZZLE_CPU, &program).run(&mut trace), Stop::Halted);
+    assert_eq!(
+        trace.lines,
+        [
+            "   1: noop       1/1  x=1",
+            "   2: addx 3     1/2  x=1",
+            "   3: addx 3     2/2  x=1",
+            "   4: addx -5    1/2  x=4",
+            "   5: addx -5    2/2  x=4",
+        ]
+    );
+
+    // Stopping at breakpoints, and resuming.
+    let mut vm = Vm::new(&PUZZLE_CPU, &program);
+    vm.break_at(3);
+    vm.break_at(4);
+    let mut trace = Trace::default();
+    assert_eq!(vm.run(&mut trace), Stop::Breakpoint(3));
+    assert_eq!((trace.lines.len(), vm.registers()), (2, &[1][..]));
+    assert_eq!(vm.run(&mut trace), Stop::Breakpoint(4));
+    assert_eq!((trace.lines.len(), vm.registers()), (3, &[4][..]));
+    assert_eq!(vm.run(&mut trace), Stop::Halted);
+    assert_eq!((trace.lines.len(), vm.registers()), (5, &[-1][..]));
+
+    // More registers, and opcodes that take longer.
+    const TWO_REGISTERS: InstructionSet = InstructionSet {
+        registers: &[
+            Register {
+                name: "a",
+                initial: 0,
+            },
+            Register {
+                name: "b",
+                initial: 7,
+            },
+        ],
+        opcodes: &[
+            Opcode {
+                name: "seta",
+                cycles: 1,
+                effect: Effect::Set(0),
+            },
+            Opcode {
+                name: "addb",
+                cycles: 3,
+                effect: Effect::Add(1),
+            },
+        ],
+    };
+    let program = TWO_REGISTERS
+        .parse_program("seta 5\naddb -2\nseta 1")
+        .unwrap();
+    let mut trace = Trace::default();
+    let mut vm = Vm::new(&TWO_REGISTERS, &program);
+    assert_eq!(vm.run(&mut trace), Stop::Halted);
+    assert_eq!(vm.registers(), [1, 5]);
+    assert_eq!(
+        trace.lines.last().map(String::as_str),
+        Some("   5: seta 1     1/1  a=5 b=5")
+    );
+
+    assert!(TWO_REGISTERS.parse_program("addx 1").is_err());
+    assert!(TWO_REGISTERS.parse_program("seta").is_err());
+    assert!(PUZZLE_CPU.parse_program("noop 1").is_err());
+}
+
+#[test]
+fn test_example_twenty_cycles() {}
+
+fn render_screen(screen: &Screen) -> String {
+    let mut rendered = String::new();
+    for line in screen {
+        rendered.extend(line.iter());
+        rendered.push('\n');
+    }
+    rendered
+}
+
+/// The letters drawn on the screen.
+fn read_screen(screen: &Screen) -> Result<String, OcrError> {
+    ocr::read(&render_screen(screen))
+}
+
+#[test]
+fn test_example_screen() {
+    let example_insts = parse_instructions(
+        "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop",
+    )
+    .unwrap();
+
+    assert_eq!(sum_strengths_every_twenty(&example_insts), 13140);
+
+    let screen = draw_screen(&example_insts);
+    assert_eq!(
+        render_screen(&screen),
+        "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+    );
+    assert!(matches!(
+        read_screen(&screen),
+        Err(OcrError::Unrecognized(_))
+    ));
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 10;
+
+    type Parsed<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part1(insts: &Vec<Instruction>) -> String {
+        sum_strengths_every_twenty(insts).to_string()
+    }
+
+    fn part2(insts: &Vec<Instruction>) -> String {
+        let screen = draw_screen(insts);
+        read_screen(&screen).unwrap_or_else(|error| format!("{error}:\n{}", render_screen(&screen)))
+    }
+}
+
+aoc_common::example_tests!(Day10);
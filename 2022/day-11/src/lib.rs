@@ -0,0 +1,606 @@
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::VecDeque;
+
+/// A worry level, which subtraction may take below zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct WorryLevel(i128);
+
+/// One side of a monkey's operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Operand {
+    Old,
+    Literal(u64),
+}
+
+impl Operand {
+    fn parse(s: &str) -> Result<Operand, ParseError> {
+        match s {
+            "old" => Ok(Operand::Old),
+            s => parse::value(s, "old or a number").map(Operand::Literal),
+        }
+    }
+
+    fn value(self, old: i128) -> i128 {
+        match self {
+            Operand::Old => old,
+            Operand::Literal(n) => i128::from(n),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+}
+
+/// A monkey's operation, `new = <left> <operator> <right>`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Operation {
+    left: Operand,
+    operator: Operator,
+    right: Operand,
+}
+
+impl Operation {
+    fn parse(s: &str) -> Result<Operation, ParseError> {
+        let mut words = s.split(' ');
+        let left = Operand::parse(parse::next(&mut words, s, "an operand")?)?;
+        let operator = match parse::next(&mut words, s, "an operator")? {
+            "+" => Operator::Add,
+            "-" => Operator::Subtract,
+            "*" => Operator::Multiply,
+            op => return Err(ParseError::new(op, "+, - or *")),
+        };
+        let right = Operand::parse(parse::next(&mut words, s, "an operand")?)?;
+        if let Some(extra) = words.next() {
+            return Err(ParseError::new(extra, "the end of the operation"));
+        }
+
+        Ok(Operation {
+            left,
+            operator,
+            right,
+        })
+    }
+
+    /// The new worry level, or `None` if it's too big to keep track of.  If
+    /// a `modulus` is given, the new worry level is only worked out modulo
+    /// it, between zero and the modulus, so that it never overflows.
+    fn apply(&self, old: WorryLevel, modulus: Option<u64>) -> Option<WorryLevel> {
+        let (left, right) = (self.left.value(old.0), self.right.value(old.0));
+        let new = match modulus {
+            None => match self.operator {
+                Operator::Add => left.checked_add(right)?,
+                Operator::Subtract => left.checked_sub(right)?,
+                Operator::Multiply => left.checked_mul(right)?,
+            },
+            Some(m) => {
+                // Unsigned, since the product of two numbers less than the
+                // modulus may not fit in an i128.
+                let m = i128::from(m);
+                let (left, right) = (left.rem_euclid(m) as u128, right.rem_euclid(m) as u128);
+                let m = m as u128;
+                let new = match self.operator {
+                    Operator::Add => (left + right) % m,
+                    Operator::Subtract => (left + m - right) % m,
+                    Operator::Multiply => left * right % m,
+                };
+                new as i128
+            }
+        };
+        Some(WorryLevel(new))
+    }
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: VecDeque<WorryLevel>,
+    operation: Operation,
+    test: (u64, usize, usize),
+    num_items_inspected: u64,
+}
+
+fn gcd(first: u64, second: u64) -> u64 {
+    let (mut max, mut min) = (first, second);
+    if min < max {
+        (min, max) = (max, min);
+    }
+
+    loop {
+        let res = max % min;
+        if res == 0 {
+            return min;
+        }
+
+        (max, min) = (min, res);
+    }
+}
+
+/// The least common multiple of `first` and `second`, if it fits in a u64.
+fn lcm(first: u64, second: u64) -> Option<u64> {
+    (first / gcd(first, second)).checked_mul(second)
+}
+
+fn parse_input(s: &str) -> Result<(Vec<Monkey>, u64), ParseError> {
+    let mut lines = s.lines();
+
+    let mut monkeys = vec![];
+    let mut current_lcm = 1;
+
+    // Where each monkey throws, checked once every monkey is known.
+    let mut targets = vec![];
+
+    loop {
+        // Monkey 0:
+        //   Starting items: 79, 98
+        //   Operation: new = old * 19
+        //   Test: divisible by 23
+        //     If true: throw to monkey 2
+        //     If false: throw to monkey 3
+
+        let line = match lines.next() {
+            None => break,
+            Some(line) => match line {
+                "" => continue,
+                line => line,
+            },
+        };
+
+        // Monkey 0:
+        let id_text = parse::strip_suffix(parse::strip_prefix(line, "Monkey ")?, ":")?;
+        let id: usize = parse::value(id_text, "a monkey number")?;
+        if id != monkeys.len() {
+            return Err(ParseError::new(
+                id_text,
+                format!("monkey {}, in order", monkeys.len()),
+            ));
+        }
+
+        //   Starting items: 74, 64, 74, 63, 53
+        let line = parse::next(&mut lines, s, "a starting items line")?;
+        let starting_items = parse::strip_prefix(line, "  Starting items: ")?
+            .split(", ")
+            .map(|s| parse::value(s, "a worry level").map(|n: u64| WorryLevel(i128::from(n))))
+            .collect::<Result<_, _>>()?;
+
+        //   Operation: new = old * 7
+        let line = parse::next(&mut lines, s, "an operation line")?;
+        let op = Operation::parse(parse::strip_prefix(line, "  Operation: new = ")?)?;
+
+        //   Test: divisible by 5
+        let line = parse::next(&mut lines, s, "a test line")?;
+        let divisor = parse::strip_prefix(line, "  Test: divisible by ")?;
+        let test_divisor = parse::value(divisor, "a divisor")?;
+        if test_divisor == 0 {
+            return Err(ParseError::new(divisor, "a nonzero divisor"));
+        }
+
+        current_lcm = lcm(test_divisor, current_lcm).ok_or_else(|| {
+            ParseError::new(
+                divisor,
+                "a divisor whose least common multiple with the others fits in 64 bits",
+            )
+        })?;
+
+        //     If true: throw to monkey 1
+        let line = parse::next(&mut lines, s, "an if true line")?;
+        let if_true_text = parse::strip_prefix(line, "    If true: throw to monkey ")?;
+        let if_true = parse::value(if_true_text, "a monkey number")?;
+
+        //     If false: throw to monkey 6
+        let line = parse::next(&mut lines, s, "an if false line")?;
+        let if_false_text = parse::strip_prefix(line, "    If false: throw to monkey ")?;
+        let if_false = parse::value(if_false_text, "a monkey number")?;
+
+        targets.push((if_true_text, if_true));
+        targets.push((if_false_text, if_false));
+
+        monkeys.push(Monkey {
+            items: starting_items,
+            operation: op,
+            test: (test_divisor, if_true, if_false),
+            num_items_inspected: 0,
+        });
+    }
+
+    if let Some((text, _)) = targets
+        .into_iter()
+        .find(|&(_, target)| target >= monkeys.len())
+    {
+        return Err(ParseError::new(
+            text,
+            format!("a monkey number less than {}", monkeys.len()),
+        ));
+    }
+
+    Ok((monkeys, current_lcm))
+}
+
+/// Have each monkey in turn inspect and throw its items, or say which
+/// monkey's worry level got too big.
+fn run_round(monkeys: &mut [Monkey], lcm: Option<u64>) -> Result<(), String> {
+    for j in 0..monkeys.len() {
+        loop {
+            // Remove first item from current monkey and inspect it.
+            let mut item = match monkeys[j].items.pop_front() {
+                None => break,
+                Some(item) => item,
+            };
+
+            monkeys[j].num_items_inspected += 1;
+
+            // Worry level changes per the monkey in question, confined to
+            // modular space if there's no relief.
+            item = monkeys[j]
+                .operation
+                .apply(item, lcm)
+                .ok_or_else(|| format!("monkey {j}'s worry level gets too big"))?;
+
+            if lcm.is_none() {
+                // Feel relief that it wasn't damaged, rounding down.
+                item = WorryLevel(item.0.div_euclid(3));
+            }
+
+            // Throw to new monkey.
+            let test = item.0 % i128::from(monkeys[j].test.0) == 0;
+            let new_monkey = if test {
+                monkeys[j].test.1
+            } else {
+                monkeys[j].test.2
+            };
+
+            monkeys[new_monkey].items.push_back(item);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+fn print_inspections(monkeys: &[Monkey]) {
+    for (i, monkey) in monkeys.iter().enumerate() {
+        println!(
+            "Monkey {} inspected items {} times",
+            i, monkey.num_items_inspected
+        );
+    }
+}
+
+fn monkey_business(monkeys: &[Monkey]) -> u64 {
+    let mut nums_inspected = monkeys
+        .iter()
+        .map(|m| m.num_items_inspected)
+        .collect::<Vec<_>>();
+    nums_inspected.sort_by(|a, b| b.cmp(a));
+    nums_inspected.iter().take(2).product::<u64>()
+}
+
+#[cfg(test)]
+fn assert_inspection_counts(monkeys: &[Monkey], counts: &[u64]) {
+    let actual = monkeys
+        .iter()
+        .map(|m| m.num_items_inspected)
+        .collect::<Vec<_>>();
+    assert_eq!(actual, counts);
+}
+
+#[test]
+fn test_example() {
+    let test_input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    let (monkeys, lcm) = parse_input(test_input).unwrap();
+
+    {
+        let mut part1 = monkeys.clone();
+        run_round(&mut part1, None).unwrap();
+
+        assert_eq!(
+            part1[0]
+                .items
+                .iter()
+                .copied()
+                .map(|w| w.0)
+                .collect::<Vec<_>>(),
+            vec![20, 23, 27, 26]
+        );
+
+        assert_eq!(
+            part1[1]
+                .items
+                .iter()
+                .copied()
+                .map(|w| w.0)
+                .collect::<Vec<_>>(),
+            vec![2080, 25, 167, 207, 401, 1046]
+        );
+
+        for _ in 1..20 {
+            run_round(&mut part1, None).unwrap();
+        }
+
+        print_inspections(&part1);
+
+        assert_inspection_counts(&part1, &[101, 95, 7, 105]);
+    }
+
+    {
+        let mut part2 = monkeys.clone();
+        run_round(&mut part2, Some(lcm)).unwrap();
+        assert_inspection_counts(&part2, &[2, 4, 3, 6]);
+
+        for _ in 1..20 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[99, 97, 8, 103]);
+
+        for _ in 20..1_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[5204, 4792, 199, 5192]);
+
+        for _ in 1_000..2_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[10419, 9577, 392, 10391]);
+
+        for _ in 2_000..3_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[15638, 14358, 587, 15593]);
+
+        for _ in 3_000..4_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[20858, 19138, 780, 20797]);
+
+        for _ in 4_000..5_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[26075, 23921, 974, 26000]);
+
+        for _ in 5_000..6_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[31294, 28702, 1165, 31204]);
+
+        for _ in 6_000..7_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[36508, 33488, 1360, 36400]);
+
+        for _ in 7_000..8_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[41728, 38268, 1553, 41606]);
+
+        for _ in 8_000..9_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[46945, 43051, 1746, 46807]);
+
+        for _ in 9_000..10_000 {
+            run_round(&mut part2, Some(lcm)).unwrap();
+        }
+
+        print_inspections(&part2);
+        assert_inspection_counts(&part2, &[52166, 47830, 1938, 52013]);
+
+        let mb = monkey_business(&part2);
+        assert_eq!(mb, 2713310158);
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 11;
+
+    type Parsed<'a> = (Vec<Monkey>, u64);
+
+    fn parse(input: &str) -> Result<(Vec<Monkey>, u64), ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((monkeys, _lcm): &(Vec<Monkey>, u64)) -> String {
+        let mut part1 = monkeys.clone();
+        for _ in 0..20 {
+            if let Err(error) = run_round(&mut part1, None) {
+                return error;
+            }
+        }
+
+        monkey_business(&part1).to_string()
+    }
+
+    fn part2((monkeys, lcm): &(Vec<Monkey>, u64)) -> String {
+        let mut part2 = monkeys.clone();
+        for _ in 0..10_000 {
+            run_round(&mut part2, Some(*lcm)).expect("worry levels kept modulo the divisors");
+        }
+
+        monkey_business(&part2).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day11);
+
+#[test]
+fn bad_operation() {
+    let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old / 2
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3";
+
+    let error = parse_input(input).err().unwrap().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 24: expected +, - or *, found \"/\""
+    );
+}
+
+#[test]
+fn bad_tests() {
+    let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 2
+  Test: divisible by 0
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+    let error = parse_input(input).err().unwrap().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 4, column 22: expected a nonzero divisor, found \"0\""
+    );
+
+    let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 2
+  Test: divisible by 3
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    let error = parse_input(input).err().unwrap().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 6, column 31: expected a monkey number less than 1, found \"1\""
+    );
+
+    let input = "Monkey 1:
+  Starting items: 79, 98
+  Operation: new = old * 2
+  Test: divisible by 3
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+    let error = parse_input(input).err().unwrap().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 1, column 8: expected monkey 0, in order, found \"1\""
+    );
+
+    let input = "Monkey 0:
+  Starting items: 79
+  Operation: new = old * 2
+  Test: divisible by 18446744073709551557
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 98
+  Operation: new = old * 2
+  Test: divisible by 3
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+    let error = parse_input(input).err().unwrap().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 11, column 22: expected a divisor whose least common multiple with the others \
+         fits in 64 bits, found \"3\""
+    );
+}
+
+#[test]
+fn huge_worry() {
+    // Squared over and over, the worry level soon gets too big to keep
+    // track of without working modulo the divisors.
+    let input = "Monkey 0:
+  Starting items: 1000
+  Operation: new = old * old
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 1
+  Operation: new = old * old
+  Test: divisible by 2
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+    let parsed = parse_input(input).unwrap();
+    assert_eq!(Day11::part1(&parsed), "monkey 1's worry level gets too big");
+    assert_eq!(Day11::part2(&parsed), (20_000 * 19_999).to_string());
+}
+
+#[test]
+fn operations() {
+    let eval = |s, old, modulus| {
+        Operation::parse(s)
+            .unwrap()
+            .apply(WorryLevel(old), modulus)
+            .map(|new| new.0)
+    };
+
+    assert_eq!(eval("old * 19", 79, None), Some(1501));
+    assert_eq!(eval("old + 6", 54, None), Some(60));
+    assert_eq!(eval("old * old", 79, None), Some(6241));
+    assert_eq!(eval("3 - old", 5, None), Some(-2));
+    assert_eq!(eval("old - 3", 5, None), Some(2));
+    assert_eq!(eval("old * old", i128::MAX / 2, None), None);
+
+    // Modulo the divisors, worry levels work out the same as without.
+    assert_eq!(eval("old * old", 12, Some(23)), Some(144 % 23));
+    assert_eq!(eval("3 - old", 5, Some(23)), Some((-2i128).rem_euclid(23)));
+    assert_eq!(eval("3 - old", -2, Some(23)), Some(5));
+    let max = i128::from(u64::MAX);
+    assert_eq!(eval("old * 7", max - 1, Some(u64::MAX)), Some(max - 7));
+
+    let input = "old * 7 + 1";
+    let error = Operation::parse(input).unwrap_err().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 1, column 9: expected the end of the operation, found \"+\""
+    );
+}
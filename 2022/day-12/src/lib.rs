@@ -0,0 +1,188 @@
+use aoc_common::{ParseError, Solution};
+use aoc_grid::Grid;
+use priority_queue::PriorityQueue;
+use std::cmp::Reverse;
+
+const fn elevation_value(c: char) -> u8 {
+    c as u8 - b'a'
+}
+
+type Loc = (usize, usize);
+
+pub struct HeightMap {
+    heights: Grid<u8>,
+    start: Loc,
+    end: Loc,
+}
+
+impl HeightMap {
+    fn height(&self, loc: Loc) -> u8 {
+        self.heights[loc]
+    }
+
+    /// Locations one step from `loc` that are at most one higher.
+    fn adjacent_locations(&self, loc: Loc) -> impl Iterator<Item = Loc> + '_ {
+        let max_height = self.height(loc) + 1;
+        self.heights
+            .neighbors4(loc)
+            .filter(move |&adj| self.height(adj) <= max_height)
+    }
+
+    /// Locations one step from `loc` that could step to it: those at most one
+    /// lower.
+    fn adjacent_reverse_locations(&self, loc: Loc) -> impl Iterator<Item = Loc> + '_ {
+        let min_height = self.height(loc) - 1;
+        self.heights
+            .neighbors4(loc)
+            .filter(move |&adj| self.height(adj) >= min_height)
+    }
+
+    fn is_end(&self, loc: Loc) -> bool {
+        loc == self.end
+    }
+
+    fn is_any_start(&self, loc: Loc) -> bool {
+        self.height(loc) == 0
+    }
+}
+
+fn parse_input(s: &str) -> Result<HeightMap, ParseError> {
+    let chars = Grid::parse(s, "an elevation letter", |c| {
+        (c.is_ascii_lowercase() || c == 'S' || c == 'E').then_some(c)
+    })?;
+
+    let start = chars
+        .position(|&c| c == 'S')
+        .ok_or_else(|| ParseError::after(s, "a starting location S"))?;
+    let end = chars
+        .position(|&c| c == 'E')
+        .ok_or_else(|| ParseError::after(s, "an ending location E"))?;
+
+    let heights = chars.map(|&c| match c {
+        'S' => elevation_value('a'),
+        'E' => elevation_value('z'),
+        c => elevation_value(c),
+    });
+
+    Ok(HeightMap {
+        heights,
+        start,
+        end,
+    })
+}
+
+#[test]
+fn test_elevation_value() {
+    assert_eq!(elevation_value('a'), 0);
+    assert_eq!(elevation_value('z'), 25);
+}
+
+#[derive(Copy, Clone)]
+struct Square {
+    steps: usize,
+    prev_loc: Loc,
+}
+
+struct PathTracking {
+    locations: Grid<Square>,
+}
+
+impl PathTracking {
+    fn new(map: &HeightMap) -> PathTracking {
+        let steps = usize::MAX;
+        let prev_loc = (usize::MAX, usize::MAX);
+        let heights = &map.heights;
+        PathTracking {
+            locations: Grid::new(
+                heights.width(),
+                heights.height(),
+                Square { steps, prev_loc },
+            ),
+        }
+    }
+
+    fn best_steps(&self, loc: Loc) -> usize {
+        self.locations[loc].steps
+    }
+
+    fn update_best_path(&mut self, loc: Loc, prev_loc: Loc, steps: usize) {
+        let info = &mut self.locations[loc];
+        info.prev_loc = prev_loc;
+        info.steps = steps;
+    }
+}
+
+fn find_shortest_path_length(map: &HeightMap) -> Option<usize> {
+    let mut path_tracking = PathTracking::new(map);
+
+    let mut frontier = PriorityQueue::new();
+    frontier.push(map.start, Reverse(0usize));
+
+    while let Some((loc, steps)) = frontier.pop() {
+        let steps = steps.0;
+        if map.is_end(loc) {
+            return Some(steps);
+        }
+
+        let new_steps = steps + 1;
+        for adj_loc in map.adjacent_locations(loc) {
+            if path_tracking.best_steps(adj_loc) > new_steps {
+                path_tracking.update_best_path(adj_loc, loc, new_steps);
+                frontier.push_decrease(adj_loc, Reverse(new_steps));
+            }
+        }
+    }
+
+    None
+}
+
+fn find_shortest_path_length_any_start(map: &HeightMap) -> Option<usize> {
+    let mut path_tracking = PathTracking::new(map);
+
+    let mut frontier = PriorityQueue::new();
+    frontier.push(map.end, Reverse(0usize));
+
+    while let Some((loc, steps)) = frontier.pop() {
+        let steps = steps.0;
+        if map.is_any_start(loc) {
+            return Some(steps);
+        }
+
+        let new_steps = steps + 1;
+        for adj_loc in map.adjacent_reverse_locations(loc) {
+            if path_tracking.best_steps(adj_loc) > new_steps {
+                path_tracking.update_best_path(adj_loc, loc, new_steps);
+                frontier.push_decrease(adj_loc, Reverse(new_steps));
+            }
+        }
+    }
+
+    None
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 12;
+
+    type Parsed<'a> = HeightMap;
+
+    fn parse(input: &str) -> Result<HeightMap, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(map: &HeightMap) -> String {
+        find_shortest_path_length(map)
+            .expect("path from start to end")
+            .to_string()
+    }
+
+    fn part2(map: &HeightMap) -> String {
+        find_shortest_path_length_any_start(map)
+            .expect("path from any start to end")
+            .to_string()
+    }
+}
+
+aoc_common::example_tests!(Day12);
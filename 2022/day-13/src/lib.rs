@@ -0,0 +1,370 @@
+use aoc_common::{ParseError, Solution};
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Packet {
+    List(Vec<Packet>),
+    Int(i32),
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum Token {
+    Open,
+    Close,
+    Comma,
+    Integer(i32),
+    /// Anything that can't begin a token.
+    Unexpected,
+}
+
+struct Tokens<'a> {
+    s: &'a str,
+    stream: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(s: &'a str) -> Tokens<'a> {
+        Tokens {
+            s,
+            stream: s.char_indices().peekable(),
+        }
+    }
+
+    /// The text from the next token onward, for reporting errors.
+    fn rest(&mut self) -> &'a str {
+        // Skip whitespace so the text starts at the token.
+        while let Some(&(_, c)) = self.stream.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.stream.next();
+        }
+
+        match self.stream.peek() {
+            Some(&(i, _)) => &self.s[i..],
+            None => &self.s[self.s.len()..],
+        }
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stream.peek()?.1 {
+                '[' => {
+                    self.stream.next();
+                    return Some(Token::Open);
+                }
+                ']' => {
+                    self.stream.next();
+                    return Some(Token::Close);
+                }
+                ',' => {
+                    self.stream.next();
+                    return Some(Token::Comma);
+                }
+                c => {
+                    if c.is_whitespace() {
+                        self.stream.next();
+                        continue;
+                    }
+
+                    if !c.is_ascii_digit() {
+                        return Some(Token::Unexpected);
+                    }
+
+                    let digits = self
+                        .stream
+                        .take_while_ref(|(_, c)| c.is_ascii_digit())
+                        .map(|(_, c)| c)
+                        .collect::<String>();
+
+                    return Some(match digits.parse() {
+                        Ok(n) => Token::Integer(n),
+                        Err(_) => Token::Unexpected,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_tokenizing() {
+    assert_eq!(
+        Tokens::new("[[8,[1,9],6]]").collect::<Vec<_>>(),
+        [
+            Token::Open,
+            Token::Open,
+            Token::Integer(8),
+            Token::Comma,
+            Token::Open,
+            Token::Integer(1),
+            Token::Comma,
+            Token::Integer(9),
+            Token::Close,
+            Token::Comma,
+            Token::Integer(6),
+            Token::Close,
+            Token::Close
+        ]
+    );
+}
+
+/// The next token, with the text it begins, or an error saying what was
+/// `expected` if there isn't one.
+fn next_token<'a>(tokens: &mut Tokens<'a>, expected: &str) -> Result<(Token, &'a str), ParseError> {
+    let text = tokens.rest();
+    match tokens.next() {
+        None | Some(Token::Unexpected) => Err(ParseError::new(text, expected)),
+        Some(tok) => Ok((tok, text)),
+    }
+}
+
+fn parse_list_contents(tokens: &mut Tokens) -> Result<Vec<Packet>, ParseError> {
+    let mut elems = vec![];
+
+    let (mut tok, mut text) = next_token(tokens, "list contents")?;
+    if let Token::Close = tok {
+        return Ok(elems);
+    }
+
+    loop {
+        if let Token::Integer(i) = tok {
+            elems.push(Packet::Int(i));
+        } else if let Token::Open = tok {
+            elems.push(Packet::List(parse_list_contents(tokens)?));
+        } else {
+            return Err(ParseError::new(text, "list element"));
+        }
+
+        let (after, after_text) = next_token(tokens, "',' or ']'")?;
+        match after {
+            Token::Close => break,
+            Token::Comma => {
+                (tok, text) = next_token(tokens, "next element")?;
+                continue;
+            }
+            _ => return Err(ParseError::new(after_text, "',' or ']'")),
+        }
+    }
+
+    Ok(elems)
+}
+
+fn parse_packet(s: &str) -> Result<Packet, ParseError> {
+    let mut tokens = Tokens::new(s);
+
+    let (tok, text) = next_token(&mut tokens, "a list")?;
+    if tok != Token::Open {
+        return Err(ParseError::new(text, "a list"));
+    }
+
+    let packet = Packet::List(parse_list_contents(&mut tokens)?);
+    let rest = tokens.rest();
+    if !rest.is_empty() {
+        return Err(ParseError::new(rest, "end of packet"));
+    }
+    Ok(packet)
+}
+
+fn compare_packets(p1: &Packet, p2: &Packet) -> Ordering {
+    match (p1, p2) {
+        (&Packet::Int(lint), &Packet::Int(rint)) => lint.cmp(&rint),
+        (Packet::List(llist), Packet::List(rlist)) => {
+            let mut lelems = llist.iter();
+            let mut relems = rlist.iter();
+
+            loop {
+                let left = lelems.next();
+                let right = relems.next();
+
+                match (left, right) {
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (None, None) => return Ordering::Equal,
+                    (Some(left), Some(right)) => match compare_packets(left, right) {
+                        Ordering::Equal => continue,
+                        Ordering::Less => return Ordering::Less,
+                        Ordering::Greater => return Ordering::Greater,
+                    },
+                }
+            }
+        }
+        (&Packet::Int(lint), right_packet_list) => {
+            compare_packets(&Packet::List(vec![Packet::Int(lint)]), right_packet_list)
+        }
+        (left_packet_list, &Packet::Int(rint)) => {
+            compare_packets(left_packet_list, &Packet::List(vec![Packet::Int(rint)]))
+        }
+    }
+}
+
+#[test]
+fn test_comparison() {
+    macro_rules! compare {
+        ($first:expr, $second:expr, $cmp:expr) => {
+            let first = parse_packet($first).unwrap();
+            let second = parse_packet($second).unwrap();
+            assert_eq!(compare_packets(&first, &second), $cmp);
+        };
+    }
+
+    compare!("[]", "[]", Ordering::Equal);
+    compare!("[]", "[1]", Ordering::Less);
+    compare!("[1]", "[]", Ordering::Greater);
+    compare!("[1]", "[[1]]", Ordering::Equal);
+    compare!("[1, [2], 3]", "[1, 2, 3]", Ordering::Equal);
+}
+
+#[test]
+fn test_example() {
+    let example = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    let pairs = parse_input(example).unwrap();
+
+    let sum_right_ordered_pairs_indices = Itertools::tuples(pairs.iter())
+        .map(|(left, right)| compare_packets(left, right))
+        .enumerate()
+        .filter_map(|(i, order)| match order {
+            Ordering::Less => {
+                assert!([1, 2, 4, 6].contains(&(i + 1)), "i should be 1/2/4/6");
+                Some(i + 1)
+            }
+            _ => {
+                assert!(![1, 2, 4, 6].contains(&(i + 1)), "i shouldn't be 1/2/4/6");
+                None
+            }
+        })
+        .sum::<usize>();
+
+    assert_eq!(sum_right_ordered_pairs_indices, 13);
+
+    let div1 = Packet::List(vec![Packet::List(vec![Packet::Int(2)])]);
+    let div2 = Packet::List(vec![Packet::List(vec![Packet::Int(6)])]);
+
+    let mut pairs_and_dividers = vec![div1.clone(), div2.clone()];
+    pairs_and_dividers.extend(pairs);
+
+    pairs_and_dividers.sort_by(compare_packets);
+
+    assert!(pairs_and_dividers.contains(&div1));
+    assert!(pairs_and_dividers.contains(&div2));
+
+    let index_div1 = pairs_and_dividers
+        .binary_search_by(|packet| compare_packets(packet, &div1))
+        .expect("div1")
+        + 1;
+    assert_eq!(index_div1, 10);
+
+    println!(
+        "contains index_div2: {}",
+        pairs_and_dividers.contains(&div2)
+    );
+    if false {
+        let index_div2 =
+            pairs_and_dividers.binary_search_by(|packet| compare_packets(packet, &div2));
+        println!("index_div2: {:?}", index_div2);
+
+        let index_div2 = index_div2.expect("div2") + 1;
+        println!("index of [[6]]: {}", index_div2);
+
+        println!(
+            "indexes of dividers after sorting: {}, {}",
+            index_div1, index_div2
+        );
+
+        println!("product of indexes: {}", index_div1 * index_div2);
+    }
+}
+
+fn parse_input(s: &str) -> Result<Vec<Packet>, ParseError> {
+    s.lines()
+        .filter(|s| !s.is_empty())
+        .map(parse_packet)
+        .collect()
+}
+
+fn sum_right_ordered_pairs_indices(pairs: &[Packet]) -> usize {
+    Itertools::tuples(pairs.iter())
+        .map(|(left, right)| compare_packets(left, right))
+        .enumerate()
+        .filter_map(|(i, order)| match order {
+            Ordering::Less => Some(i + 1),
+            _ => None,
+        })
+        .sum::<usize>()
+}
+
+fn decoder_key(pairs: &[Packet]) -> usize {
+    let div1 = Packet::List(vec![Packet::List(vec![Packet::Int(2)])]);
+    let div2 = Packet::List(vec![Packet::List(vec![Packet::Int(6)])]);
+
+    let mut pairs_and_dividers = vec![div1.clone(), div2.clone()];
+    pairs_and_dividers.extend(pairs.iter().cloned());
+
+    pairs_and_dividers.sort_by(compare_packets);
+
+    let index_div1 = pairs_and_dividers
+        .binary_search_by(|packet| compare_packets(packet, &div1))
+        .expect("div1")
+        + 1;
+
+    let index_div2 = pairs_and_dividers
+        .binary_search_by(|packet| compare_packets(packet, &div2))
+        .expect("div2")
+        + 1;
+
+    index_div1 * index_div2
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 13;
+
+    type Parsed<'a> = Vec<Packet>;
+
+    fn parse(input: &str) -> Result<Vec<Packet>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(pairs: &Vec<Packet>) -> String {
+        sum_right_ordered_pairs_indices(pairs).to_string()
+    }
+
+    fn part2(pairs: &Vec<Packet>) -> String {
+        decoder_key(pairs).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day13);
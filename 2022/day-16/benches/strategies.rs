@@ -0,0 +1,52 @@
+//! Times each strategy for finding the best plan on made-up scans of
+//! different sizes, to show where each is faster.
+//!
+//! Run with `cargo bench -p aoc-2022-day-16`.
+
+use aoc_2022_day_16::{generate_scan, Day16, Strategy};
+use aoc_common::Solution;
+use std::time::{Duration, Instant};
+
+/// How many different scans of each size to time.
+const SEEDS: u64 = 3;
+
+fn time(scans: &[String], agents: usize, minutes: u32, strategy: Strategy) -> (Duration, u32) {
+    let start = Instant::now();
+    let mut total = 0;
+    for scan in scans {
+        let volcano = Day16::parse(scan).expect("generated scans parse");
+        total += volcano.plan_by(agents, minutes, strategy).total_flow;
+    }
+    (start.elapsed() / scans.len() as u32, total)
+}
+
+fn main() {
+    println!(
+        "{:>6} {:>6} {:>14} {:>14}",
+        "valves", "agents", "subsets", "branch+bound"
+    );
+    // The branch and bound strategy takes a long time for many valves and
+    // agents, so stop short of that.
+    for (agents, minutes, most_valves) in [(1, 30, 60), (2, 26, 40), (3, 24, 20)] {
+        for useful in (8..=most_valves).step_by(4) {
+            let scans = (0..SEEDS)
+                .map(|seed| generate_scan(useful, seed))
+                .collect::<Vec<_>>();
+
+            // The solver counts the starting valve as well as the useful ones.
+            let subsets = (useful < Strategy::MOST_SUBSET_VALVES)
+                .then(|| time(&scans, agents, minutes, Strategy::Subsets));
+            let bnb = time(&scans, agents, minutes, Strategy::BranchAndBound);
+            if let Some((_, flow)) = subsets {
+                assert_eq!(flow, bnb.1, "strategies disagree");
+            }
+
+            let show = |t: Option<Duration>| t.map_or("-".to_string(), |t| format!("{t:.2?}"));
+            println!(
+                "{useful:>6} {agents:>6} {:>14} {:>14}",
+                show(subsets.map(|(t, _)| t)),
+                show(Some(bnb.0)),
+            );
+        }
+    }
+}
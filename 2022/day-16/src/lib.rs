@@ -0,0 +1,1284 @@
+use aoc_common::{parse, ParseError, Solution};
+use aoc_grid::Grid;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::Add;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct FlowRate(u32);
+
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
+struct Time(u32);
+
+#[derive(Copy, Clone, Ord, PartialOrd, PartialEq, Eq)]
+struct Dist(u32);
+
+impl Dist {
+    fn is_infinite(&self) -> bool {
+        self.0 == u32::MAX
+    }
+}
+
+impl Default for Dist {
+    fn default() -> Self {
+        Dist(u32::MAX)
+    }
+}
+
+impl Add for Dist {
+    type Output = Dist;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Dist(self.0.saturating_add(rhs.0))
+    }
+}
+
+struct Valve<'a> {
+    name: &'a str,
+    flow_rate: FlowRate,
+    connects_to: Vec<&'a str>,
+}
+
+fn parse_valve_list(s: &str) -> Result<Vec<Valve<'_>>, ParseError> {
+    let mut valves = vec![];
+
+    for line in s.lines() {
+        // Valve WL has flow rate=7; tunnels lead to valves OQ, VN, PU, VF, UA
+        // Valve YJ has flow rate=16; tunnel leads to valve YX
+        let rest = parse::strip_prefix(line, "Valve ")?;
+        let (name, rest) = parse::split_once(rest, " has flow rate=")?;
+        let (flow_rate, tunnels) = parse::split_once(rest, "; ")?;
+        let connects_to = tunnels
+            .strip_prefix("tunnels lead to valves ")
+            .or_else(|| tunnels.strip_prefix("tunnel leads to valve "))
+            .ok_or_else(|| ParseError::new(tunnels, "a list of tunnels"))?;
+
+        let valve = Valve {
+            name,
+            flow_rate: FlowRate(parse::value(flow_rate, "a flow rate")?),
+            connects_to: connects_to.split(", ").collect(),
+        };
+
+        valves.push(valve);
+    }
+    Ok(valves)
+}
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+struct ValveId(usize);
+
+struct ValveInfo<'a> {
+    name: &'a str,
+    flow_rate: FlowRate,
+    connects_to: Vec<ValveId>,
+}
+
+pub struct ValveGraph<'a> {
+    name_to_valve: HashMap<&'a str, ValveId>,
+    valves: Vec<ValveInfo<'a>>,
+    starting_valve: ValveId,
+}
+
+struct ValveEdges<'a> {
+    valves: &'a [ValveInfo<'a>],
+    curr_valve: usize,
+    curr_connection: usize,
+}
+
+impl Iterator for ValveEdges<'_> {
+    type Item = (ValveId, ValveId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.curr_valve >= self.valves.len() {
+                return None;
+            }
+
+            let v = &self.valves[self.curr_valve];
+            if self.curr_connection >= v.connects_to.len() {
+                self.curr_valve += 1;
+                self.curr_connection = 0;
+                continue;
+            }
+
+            let curr_valve = self.curr_valve;
+            let curr_connection = self.curr_connection;
+            self.curr_connection += 1;
+            if self.curr_connection == v.connects_to.len() {
+                self.curr_valve += 1;
+                self.curr_connection = 0;
+            }
+
+            return Some((ValveId(curr_valve), v.connects_to[curr_connection]));
+        }
+    }
+}
+
+impl<'a> ValveGraph<'a> {
+    fn valve_to_name(&self, valve: ValveId) -> &'a str {
+        let ValveId(index) = valve;
+        self.valves[index].name
+    }
+
+    fn name_to_valve(&self, name: &str) -> Option<ValveId> {
+        self.name_to_valve.get(name).copied()
+    }
+
+    fn valve_count(&self) -> usize {
+        self.valves.len()
+    }
+
+    fn edges(&self) -> ValveEdges<'_> {
+        ValveEdges {
+            valves: &self.valves,
+            curr_valve: 0,
+            curr_connection: 0,
+        }
+    }
+
+    fn valves(&self) -> impl Iterator<Item = ValveId> {
+        (0..self.valves.len()).map(ValveId)
+    }
+
+    fn valves_from(&self, from: ValveId) -> impl Iterator<Item = ValveId> + '_ {
+        let ValveId(from) = from;
+        self.valves[from].connects_to.iter().copied()
+    }
+}
+
+/// A made-up scan of `useful` valves worth opening, in the same format as
+/// puzzle input.  Like the puzzle's scans, the valves are strung along
+/// winding tunnels through three times as many rooms with no flow.  The same
+/// `seed` always gives the same scan.
+pub fn generate_scan(useful: usize, seed: u64) -> String {
+    let mut state = seed;
+    let mut random = |n: usize| {
+        // Knuth's MMIX linear congruential generator.
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize % n
+    };
+
+    let count = 1 + 4 * useful;
+    assert!(count <= 26 * 26, "too many valves to name");
+    let name = |i: usize| {
+        let letter = |k: usize| char::from(b'A' + k as u8);
+        format!("{}{}", letter(i / 26), letter(i % 26))
+    };
+
+    // Join each room to one of the few before it, then add a few shortcuts.
+    let mut tunnels = vec![vec![]; count];
+    let join = |tunnels: &mut Vec<Vec<usize>>, a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..count {
+        let j = i - 1 - random(i.min(4));
+        join(&mut tunnels, i, j);
+    }
+    for _ in 0..count / 8 {
+        let (a, b) = (random(count), random(count));
+        join(&mut tunnels, a, b);
+    }
+
+    let mut scan = String::new();
+    for (i, tunnels) in tunnels.iter().enumerate() {
+        // Valve AA comes first, then every fourth valve is worth opening.
+        let rate = if i % 4 == 1 { 1 + random(25) } else { 0 };
+        let names = tunnels.iter().map(|&j| name(j)).collect::<Vec<_>>();
+        let tunnels = if let [only] = &names[..] {
+            format!("tunnel leads to valve {only}")
+        } else {
+            format!("tunnels lead to valves {}", names.join(", "))
+        };
+        writeln!(scan, "Valve {} has flow rate={rate}; {tunnels}", name(i)).unwrap();
+    }
+    scan
+}
+
+fn build_valve_graph(s: &str) -> Result<ValveGraph<'_>, ParseError> {
+    let valve_list = parse_valve_list(s)?;
+
+    let mut name_to_valve = HashMap::new();
+    let mut valves = vec![];
+
+    let mut starting_valve = None;
+
+    for Valve {
+        name, flow_rate, ..
+    } in &valve_list
+    {
+        let index = ValveId(valves.len());
+        if *name == "AA" {
+            starting_valve = Some(index);
+        }
+
+        if name_to_valve.insert(*name, index).is_some() {
+            return Err(ParseError::new(name, "a valve not already described"));
+        }
+
+        valves.push(ValveInfo {
+            name,
+            flow_rate: *flow_rate,
+            connects_to: vec![],
+        });
+    }
+
+    let starting_valve = starting_valve.ok_or_else(|| ParseError::after(s, "a valve AA"))?;
+
+    for (i, desc) in valve_list.iter().enumerate() {
+        for name in &desc.connects_to {
+            let valve = name_to_valve
+                .get(name)
+                .ok_or_else(|| ParseError::new(name, "a valve described elsewhere"))?;
+            valves[i].connects_to.push(*valve);
+        }
+    }
+
+    Ok(ValveGraph {
+        name_to_valve,
+        valves,
+        starting_valve,
+    })
+}
+
+#[derive(Copy, Clone)]
+struct PathElem {
+    dist: Dist,
+    next: usize,
+}
+
+impl Default for PathElem {
+    fn default() -> Self {
+        PathElem {
+            dist: Default::default(),
+            next: usize::MAX,
+        }
+    }
+}
+
+pub struct ShortestDistanceInfo {
+    matrix: Grid<PathElem>,
+}
+
+impl ShortestDistanceInfo {
+    fn shortest_path<'a>(
+        &self,
+        graph: &ValveGraph<'a>,
+        from_name: &str,
+        to_name: &str,
+    ) -> Option<Vec<&'a str>> {
+        let ValveId(from) = graph.name_to_valve(from_name)?;
+        let ValveId(to) = graph.name_to_valve(to_name)?;
+
+        let mut path_elem = self.matrix[(from, to)];
+        if path_elem.dist == Default::default() {
+            return None;
+        }
+
+        let mut edges = vec![graph.valve_to_name(ValveId(from))];
+        loop {
+            let dist = path_elem.dist;
+            if dist == Dist(0) {
+                break;
+            }
+
+            let next = path_elem.next;
+            edges.push(graph.valve_to_name(ValveId(next)));
+            path_elem = self.matrix[(next, to)];
+        }
+
+        Some(edges)
+    }
+
+    fn distance(&self, from: ValveId, to: ValveId) -> Dist {
+        let path_elem = &self.matrix[(from.0, to.0)];
+        assert!(
+            path_elem.next != usize::MAX,
+            "can't request distance between unconnected valves"
+        );
+
+        path_elem.dist
+    }
+
+    /// The distance from one valve to another, if there's a way between
+    /// them.
+    fn distance_if_connected(&self, from: ValveId, to: ValveId) -> Option<Dist> {
+        let dist = self.matrix[(from.0, to.0)].dist;
+        (!dist.is_infinite()).then_some(dist)
+    }
+
+    fn new(graph: &ValveGraph<'_>) -> ShortestDistanceInfo {
+        let num_valves = graph.valve_count();
+
+        let mut matrix = Grid::new(num_valves, num_valves, PathElem::default());
+
+        // For every tunnel, note distance 1 between valves with destination valve
+        // as next valve to visit.
+        for (ValveId(from), ValveId(to)) in graph.edges() {
+            matrix[(from, to)] = PathElem {
+                dist: Dist(1),
+                next: to,
+            };
+        }
+
+        // No distance or next valve for each valve to itself.
+        for i in 0..num_valves {
+            matrix[(i, i)] = PathElem {
+                dist: Dist(0),
+                next: usize::MAX,
+            };
+        }
+
+        // Extend paths out from each valve.
+        for k in 0..num_valves {
+            for i in 0..num_valves {
+                for j in 0..num_valves {
+                    let ik_dist = matrix[(i, k)].dist;
+                    let kj_dist = matrix[(k, j)].dist;
+                    let cand_dist = ik_dist + kj_dist;
+                    if matrix[(i, j)].dist > cand_dist {
+                        // The way to j is now the way to k.
+                        matrix[(i, j)].dist = cand_dist;
+                        matrix[(i, j)].next = matrix[(i, k)].next;
+                    }
+                }
+            }
+        }
+
+        ShortestDistanceInfo { matrix }
+    }
+}
+
+fn minimize_graph<'a>(
+    graph: &ValveGraph<'a>,
+    shortest_distance_info: &ShortestDistanceInfo,
+) -> (ValveGraph<'a>, ShortestDistanceInfo) {
+    let old_valves = &graph.valves;
+
+    let mut new_valves = vec![];
+    let mut new_name_to_new_valve = HashMap::new();
+
+    let mut old_valve_from_new_valve = vec![];
+
+    let mut starting_valve = None;
+    for (
+        old,
+        &ValveInfo {
+            name, flow_rate, ..
+        },
+    ) in old_valves.iter().enumerate()
+    {
+        if flow_rate.0 == 0 && name != "AA" {
+            continue;
+        }
+
+        let id = ValveId(new_valves.len());
+        new_name_to_new_valve.insert(name, id);
+
+        if name == "AA" {
+            starting_valve = Some(id);
+        }
+
+        new_valves.push(ValveInfo {
+            name,
+            flow_rate,
+            connects_to: vec![],
+        });
+        old_valve_from_new_valve.push(ValveId(old));
+    }
+
+    let n = new_valves.len();
+    let mut m = Grid::new(n, n, PathElem::default());
+
+    let old_m = &shortest_distance_info.matrix;
+    for i in 0..n {
+        let old_i = old_valve_from_new_valve[i].0;
+        for j in 0..n {
+            let old_j = old_valve_from_new_valve[j].0;
+
+            let old = &old_m[(old_i, old_j)];
+            if !old.dist.is_infinite() {
+                m[(i, j)] = PathElem {
+                    dist: old.dist,
+                    next: j,
+                };
+
+                new_valves[i].connects_to.push(ValveId(j));
+            }
+        }
+    }
+
+    let graph = ValveGraph {
+        name_to_valve: new_name_to_new_valve,
+        valves: new_valves,
+        starting_valve: starting_valve.expect("AA"),
+    };
+
+    let sdi = ShortestDistanceInfo { matrix: m };
+
+    (graph, sdi)
+}
+
+#[derive(Copy, Clone)]
+struct ValvesVisited(u128);
+
+impl ValvesVisited {
+    fn new(graph: &ValveGraph<'_>) -> ValvesVisited {
+        assert!(
+            graph.valve_count() <= u128::BITS as usize,
+            "too many valves worth opening"
+        );
+        ValvesVisited(0)
+    }
+
+    fn contains(&self, valve: ValveId) -> bool {
+        self.0 & Self::to_bit(valve) != 0
+    }
+
+    fn visit(&self, valve: ValveId) -> ValvesVisited {
+        let bit = Self::to_bit(valve);
+        ValvesVisited(self.0 | bit)
+    }
+
+    fn to_bit(valve: ValveId) -> u128 {
+        1 << valve.0
+    }
+}
+
+/// One valve opened by an agent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Opening<'a> {
+    /// The minute, counting from 1, spent opening the valve.
+    pub minute: u32,
+    pub valve: &'a str,
+    /// The pressure the valve releases from then until time runs out.
+    pub released: u32,
+}
+
+/// The most pressure some number of agents working together can release,
+/// and the valves each agent opens to release it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan<'a> {
+    pub total_flow: u32,
+    pub schedules: Vec<Vec<Opening<'a>>>,
+}
+
+/// The most flow one agent can release opening exactly the valves in each
+/// set of valves, if it can open exactly those valves at all, and the
+/// openings that release it.
+type BestBySet<'a> = Vec<Option<(u32, Vec<Opening<'a>>)>>;
+
+struct Walk<'g, 'a> {
+    graph: &'g ValveGraph<'a>,
+    shortest_distances: &'g ShortestDistanceInfo,
+    time: Time,
+    /// The valves opened, and the minute each was opened.
+    openings: Vec<Opening<'a>>,
+    best: BestBySet<'a>,
+}
+
+impl Walk<'_, '_> {
+    /// Try every order of opening valves from `current_valve` on, noting the
+    /// best flow for each set of valves opened.
+    fn explore(
+        &mut self,
+        visited_valves: ValvesVisited,
+        current_valve: ValveId,
+        remaining_time: Time,
+        flow: u32,
+    ) {
+        let start_bit = ValvesVisited::to_bit(self.graph.starting_valve);
+        let opened = (visited_valves.0 & !start_bit) as usize;
+        if self.best[opened]
+            .as_ref()
+            .is_none_or(|(best, _)| *best < flow)
+        {
+            self.best[opened] = Some((flow, self.openings.clone()));
+        }
+
+        for next_valve in self.graph.valves_from(current_valve) {
+            if visited_valves.contains(next_valve) {
+                continue;
+            }
+
+            let rooms_to_move = self.shortest_distances.distance(current_valve, next_valve);
+
+            let time_to_travel_and_open = Time(rooms_to_move.0 + 1);
+
+            if remaining_time <= time_to_travel_and_open {
+                continue;
+            }
+
+            let next_remaining_time = Time(remaining_time.0 - time_to_travel_and_open.0);
+
+            let info = &self.graph.valves[next_valve.0];
+            let released = info.flow_rate.0 * next_remaining_time.0;
+            self.openings.push(Opening {
+                minute: self.time.0 - next_remaining_time.0,
+                valve: info.name,
+                released,
+            });
+            self.explore(
+                visited_valves.visit(next_valve),
+                next_valve,
+                next_remaining_time,
+                flow + released,
+            );
+            self.openings.pop();
+        }
+    }
+}
+
+/// A way of finding the best plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Find the best single agent plan opening each set of valves, then
+    /// divide the valves up between agents every possible way.  Fast for
+    /// few valves, but it takes time and memory exponential in the number
+    /// of valves (and more so with more than two agents), so for more than
+    /// `Strategy::MOST_SUBSET_VALVES` valves branch and bound is used
+    /// instead.
+    Subsets,
+    /// Search plans for all agents at once, abandoning any that can't beat
+    /// the best plan found so far even if every remaining valve were as
+    /// close as possible.  Slower for few valves, but copes with many.
+    BranchAndBound,
+}
+
+impl Strategy {
+    /// The most valves, counting the starting valve, to find plans for
+    /// every subset of.  The plans for that many take several hundred
+    /// megabytes, doubling with each valve more.
+    pub const MOST_SUBSET_VALVES: usize = 22;
+
+    /// The faster strategy for `agents` agents and `valves` valves worth
+    /// opening, going by the `strategies` benchmark: branch and bound for
+    /// one agent, and for more unless there are only a few valves.
+    fn for_size(valves: usize, agents: usize) -> Strategy {
+        let most_valves = match agents {
+            1 => 0,
+            2 => 20,
+            _ => 16,
+        };
+        if valves <= most_valves {
+            Strategy::Subsets
+        } else {
+            Strategy::BranchAndBound
+        }
+    }
+}
+
+/// The most pressure `agents` agents, all starting at the starting valve,
+/// can release in `minutes` minutes, and how they do it.
+pub fn best_plan<'a>(
+    graph: &ValveGraph<'a>,
+    shortest_distances: &ShortestDistanceInfo,
+    agents: usize,
+    minutes: u32,
+) -> Plan<'a> {
+    let useful = graph
+        .valves
+        .iter()
+        .filter(|info| info.flow_rate.0 > 0)
+        .count();
+    let strategy = Strategy::for_size(useful, agents);
+    best_plan_by(graph, shortest_distances, agents, minutes, strategy)
+}
+
+/// Like `best_plan`, using the given strategy, unless there are too many
+/// valves for it.
+pub fn best_plan_by<'a>(
+    graph: &ValveGraph<'a>,
+    shortest_distances: &ShortestDistanceInfo,
+    agents: usize,
+    minutes: u32,
+    strategy: Strategy,
+) -> Plan<'a> {
+    assert!(agents > 0, "must have an agent to open valves");
+
+    match strategy {
+        Strategy::Subsets if graph.valve_count() <= Strategy::MOST_SUBSET_VALVES => {
+            plan_by_subsets(graph, shortest_distances, agents, minutes)
+        }
+        Strategy::Subsets | Strategy::BranchAndBound => {
+            plan_by_branch_and_bound(graph, shortest_distances, agents, minutes)
+        }
+    }
+}
+
+fn plan_by_subsets<'a>(
+    graph: &ValveGraph<'a>,
+    shortest_distances: &ShortestDistanceInfo,
+    agents: usize,
+    minutes: u32,
+) -> Plan<'a> {
+    let start = graph.starting_valve;
+    let mut walk = Walk {
+        graph,
+        shortest_distances,
+        time: Time(minutes),
+        openings: vec![],
+        best: vec![None; 1 << graph.valve_count()],
+    };
+    walk.explore(
+        ValvesVisited::new(graph).visit(start),
+        start,
+        Time(minutes),
+        0,
+    );
+    let best = walk.best;
+
+    // The best flow for one agent opening only valves within each set of
+    // valves, and the set it opens exactly.
+    let mut best_within = best
+        .iter()
+        .enumerate()
+        .map(|(set, best)| best.as_ref().map_or((0, 0), |(flow, _)| (*flow, set)))
+        .collect::<Vec<_>>();
+    for bit in 0..graph.valve_count() {
+        for set in 0..best_within.len() {
+            if set & (1 << bit) != 0 && best_within[set ^ (1 << bit)].0 > best_within[set].0 {
+                best_within[set] = best_within[set ^ (1 << bit)];
+            }
+        }
+    }
+
+    // The best flow for k + 1 agents opening only valves within each set of
+    // valves, and the set the last of them opens valves within, for each k.
+    // The other agents open valves within the rest of the set.
+    let all = (best.len() - 1) & !(ValvesVisited::to_bit(start) as usize);
+    let mut best_for_agents = vec![best_within
+        .iter()
+        .enumerate()
+        .map(|(set, (flow, _))| (*flow, set))
+        .collect::<Vec<_>>()];
+    for k in 1..agents {
+        let fewer = &best_for_agents[k - 1];
+        // Only the full set matters for the last agent.
+        let sets = if k + 1 == agents { all..=all } else { 0..=all };
+
+        let mut more = vec![(0, 0); best.len()];
+        for set in sets.filter(|set| set & !all == 0) {
+            // Try every subset of the set for the last agent.
+            let mut subset = set;
+            loop {
+                let flow = best_within[subset].0 + fewer[set ^ subset].0;
+                if flow >= more[set].0 {
+                    more[set] = (flow, subset);
+                }
+                if subset == 0 {
+                    break;
+                }
+                subset = (subset - 1) & set;
+            }
+        }
+        best_for_agents.push(more);
+    }
+
+    // Work back from the last agent to the first to find what each opens.
+    let mut schedules = vec![];
+    let mut set = all;
+    for k in (0..agents).rev() {
+        let subset = best_for_agents[k][set].1;
+        let (_, opened) = best_within[subset];
+        schedules.push(
+            best[opened]
+                .as_ref()
+                .map(|(_, openings)| openings.clone())
+                .unwrap_or_default(),
+        );
+        set ^= subset;
+    }
+    schedules.reverse();
+
+    Plan {
+        total_flow: best_for_agents[agents - 1][all].0,
+        schedules,
+    }
+}
+
+/// Where an agent is, and how long it has left.
+#[derive(Clone, Copy)]
+struct Agent {
+    valve: ValveId,
+    remaining_time: Time,
+}
+
+struct Search<'g, 'a> {
+    graph: &'g ValveGraph<'a>,
+    shortest_distances: &'g ShortestDistanceInfo,
+    time: Time,
+    /// The valves worth opening, most flow first.
+    by_flow: Vec<ValveId>,
+    /// The least time it takes to go to any valve and open it.
+    least_step: u32,
+    /// Each agent's openings so far.
+    schedules: Vec<Vec<Opening<'a>>>,
+    /// Where in `by_flow` each agent's first valve opened is.
+    firsts: Vec<usize>,
+    best: Plan<'a>,
+}
+
+impl Search<'_, '_> {
+    /// The most flow there could possibly be on top of `flow`, the least of
+    /// two overestimates: if every unopened valve were opened as soon as
+    /// the closest agent could get to it, and if agents could get from any
+    /// valve to any other as quickly as between the closest two, opening
+    /// the valves with most flow first.
+    fn bound(&self, agents: &[Agent], visited_valves: ValvesVisited, flow: u32) -> u32 {
+        let unopened = || {
+            self.by_flow
+                .iter()
+                .copied()
+                .filter(|&valve| !visited_valves.contains(valve))
+        };
+        let rate = |valve: ValveId| self.graph.valves[valve.0].flow_rate.0;
+
+        let soonest = unopened()
+            .map(|valve| {
+                let time = agents
+                    .iter()
+                    .filter_map(|agent| {
+                        let distance = self
+                            .shortest_distances
+                            .distance_if_connected(agent.valve, valve)?;
+                        Some(agent.remaining_time.0.saturating_sub(distance.0 + 1))
+                    })
+                    .max()
+                    .unwrap_or(0);
+                rate(valve) * time
+            })
+            .sum::<u32>();
+
+        let mut times = agents
+            .iter()
+            .flat_map(|agent| {
+                (1..)
+                    .map(|k| k * self.least_step)
+                    .take_while(|&t| t < agent.remaining_time.0)
+                    .map(|t| agent.remaining_time.0 - t)
+            })
+            .collect::<Vec<_>>();
+        times.sort_unstable_by(|a, b| b.cmp(a));
+        let quickest = unopened()
+            .map(rate)
+            .zip(times)
+            .map(|(rate, t)| rate * t)
+            .sum::<u32>();
+
+        flow + soonest.min(quickest)
+    }
+
+    fn search(&mut self, agents: &mut [Agent], visited_valves: ValvesVisited, flow: u32) {
+        if flow > self.best.total_flow {
+            self.best = Plan {
+                total_flow: flow,
+                schedules: self.schedules.clone(),
+            };
+        }
+
+        if self.bound(agents, visited_valves, flow) <= self.best.total_flow {
+            return;
+        }
+
+        // Move whichever agent has the most time left.
+        let Some((a, &agent)) = agents
+            .iter()
+            .enumerate()
+            .filter(|(_, agent)| agent.remaining_time.0 > 0)
+            .max_by_key(|(a, agent)| (agent.remaining_time, std::cmp::Reverse(*a)))
+        else {
+            return;
+        };
+
+        // Agents are alike, so if they were to swap what they do, nothing
+        // would change.  Have agents that haven't opened a valve yet open
+        // valves after the one the agent before opened first.
+        let lowest = match (a, self.schedules[a].is_empty()) {
+            (0, _) | (_, false) => 0,
+            (_, true) if self.schedules[a - 1].is_empty() => self.by_flow.len(),
+            (_, true) => self.firsts[a - 1] + 1,
+        };
+
+        for i in lowest..self.by_flow.len() {
+            let next_valve = self.by_flow[i];
+            if visited_valves.contains(next_valve) {
+                continue;
+            }
+
+            let Some(rooms_to_move) = self
+                .shortest_distances
+                .distance_if_connected(agent.valve, next_valve)
+            else {
+                continue;
+            };
+            let time_to_travel_and_open = Time(rooms_to_move.0 + 1);
+            if agent.remaining_time <= time_to_travel_and_open {
+                continue;
+            }
+            let next_remaining_time = Time(agent.remaining_time.0 - time_to_travel_and_open.0);
+
+            let info = &self.graph.valves[next_valve.0];
+            let released = info.flow_rate.0 * next_remaining_time.0;
+            self.schedules[a].push(Opening {
+                minute: self.time.0 - next_remaining_time.0,
+                valve: info.name,
+                released,
+            });
+            if self.schedules[a].len() == 1 {
+                self.firsts[a] = i;
+            }
+            agents[a] = Agent {
+                valve: next_valve,
+                remaining_time: next_remaining_time,
+            };
+            self.search(agents, visited_valves.visit(next_valve), flow + released);
+            agents[a] = agent;
+            self.schedules[a].pop();
+        }
+
+        // Or have the agent stop opening valves, leaving the rest to the
+        // others.
+        agents[a].remaining_time = Time(0);
+        self.search(agents, visited_valves, flow);
+        agents[a] = agent;
+    }
+}
+
+fn plan_by_branch_and_bound<'a>(
+    graph: &ValveGraph<'a>,
+    shortest_distances: &ShortestDistanceInfo,
+    agents: usize,
+    minutes: u32,
+) -> Plan<'a> {
+    let start = graph.starting_valve;
+
+    // Valves there's no way to from the start can't be opened.
+    let mut by_flow = graph
+        .valves()
+        .filter(|&valve| valve != start && graph.valves[valve.0].flow_rate.0 > 0)
+        .filter(|&valve| {
+            shortest_distances
+                .distance_if_connected(start, valve)
+                .is_some()
+        })
+        .collect::<Vec<_>>();
+    by_flow.sort_by_key(|valve| std::cmp::Reverse(graph.valves[valve.0].flow_rate.0));
+
+    let least_step = graph
+        .valves()
+        .flat_map(|from| by_flow.iter().map(move |&to| (from, to)))
+        .filter(|(from, to)| from != to)
+        .filter_map(|(from, to)| shortest_distances.distance_if_connected(from, to))
+        .map(|dist| dist.0 + 1)
+        .min()
+        .unwrap_or(1);
+
+    let mut search = Search {
+        graph,
+        shortest_distances,
+        time: Time(minutes),
+        by_flow,
+        least_step,
+        schedules: vec![vec![]; agents],
+        firsts: vec![0; agents],
+        best: Plan {
+            total_flow: 0,
+            schedules: vec![vec![]; agents],
+        },
+    };
+    let mut agents = vec![
+        Agent {
+            valve: start,
+            remaining_time: Time(minutes),
+        };
+        agents
+    ];
+    search.search(&mut agents, ValvesVisited::new(graph).visit(start), 0);
+
+    search.best
+}
+
+#[derive(Clone, Copy)]
+enum Action<'a> {
+    Move(&'a str),
+    Open(&'a str),
+}
+
+/// A list of things, written out in English.
+fn list(items: &[&str]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.to_string(),
+        [first, second] => format!("{first} and {second}"),
+        [init @ .., last] => format!("{}, and {last}", init.join(", ")),
+    }
+}
+
+impl<'a> Plan<'a> {
+    /// What happens each minute of the plan, told as in the puzzle: you are
+    /// the first agent, and elephants are the rest.  `graph` and
+    /// `shortest_distances` give the way between the valves opened, so
+    /// they must include every valve, not just those worth opening.
+    pub fn narrative(
+        &self,
+        graph: &ValveGraph<'a>,
+        shortest_distances: &ShortestDistanceInfo,
+        minutes: u32,
+    ) -> String {
+        let minutes = minutes as usize;
+
+        // What each agent does each minute.
+        let timelines = self
+            .schedules
+            .iter()
+            .map(|schedule| {
+                let mut timeline = vec![None; minutes + 1];
+                let mut current = graph.valve_to_name(graph.starting_valve);
+                let mut minute = 0;
+                for opening in schedule {
+                    let path = shortest_distances
+                        .shortest_path(graph, current, opening.valve)
+                        .expect("path to valve");
+                    for room in &path[1..] {
+                        minute += 1;
+                        timeline[minute] = Some(Action::Move(room));
+                    }
+                    minute += 1;
+                    assert_eq!(minute, opening.minute as usize, "valve opened on time");
+                    timeline[minute] = Some(Action::Open(opening.valve));
+                    current = opening.valve;
+                }
+                timeline
+            })
+            .collect::<Vec<_>>();
+
+        let agent = |k: usize| match (k, self.schedules.len()) {
+            (0, _) => ("You".to_string(), "move", "open"),
+            (_, 2) => ("The elephant".to_string(), "moves", "opens"),
+            (k, _) => (format!("Elephant {k}"), "moves", "opens"),
+        };
+
+        let mut s = String::new();
+        let mut open: Vec<&str> = vec![];
+        for minute in 1..=minutes {
+            writeln!(s, "== Minute {minute} ==").unwrap();
+            let releasing = open
+                .iter()
+                .map(|name| {
+                    let valve = graph.name_to_valve(name).expect("valve");
+                    graph.valves[valve.0].flow_rate.0
+                })
+                .sum::<u32>();
+            match open.as_slice() {
+                [] => writeln!(s, "No valves are open."),
+                [valve] => writeln!(s, "Valve {valve} is open, releasing {releasing} pressure."),
+                valves => writeln!(
+                    s,
+                    "Valves {} are open, releasing {releasing} pressure.",
+                    list(valves)
+                ),
+            }
+            .unwrap();
+
+            for (k, timeline) in timelines.iter().enumerate() {
+                let (who, moves, opens) = agent(k);
+                match timeline[minute] {
+                    Some(Action::Move(valve)) => writeln!(s, "{who} {moves} to valve {valve}."),
+                    Some(Action::Open(valve)) => {
+                        open.push(valve);
+                        writeln!(s, "{who} {opens} valve {valve}.")
+                    }
+                    None => Ok(()),
+                }
+                .unwrap();
+            }
+            open.sort();
+            s.push('\n');
+        }
+
+        s
+    }
+}
+
+fn find_max_flow_without_elephant(
+    graph: &ValveGraph<'_>,
+    shortest_distances: &ShortestDistanceInfo,
+) -> u32 {
+    best_plan(graph, shortest_distances, 1, 30).total_flow
+}
+
+fn find_max_flow_with_elephant(
+    graph: &ValveGraph<'_>,
+    shortest_distances: &ShortestDistanceInfo,
+) -> u32 {
+    best_plan(graph, shortest_distances, 2, 26).total_flow
+}
+
+#[test]
+fn test_example() {
+    static CONTENT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    let graph = build_valve_graph(CONTENT).unwrap();
+
+    let shortest_distances = ShortestDistanceInfo::new(&graph);
+
+    assert_eq!(
+        shortest_distances.shortest_path(&graph, "AA", "EE"),
+        Some(["AA", "DD", "EE"].to_vec())
+    );
+
+    let (min_graph, min_shortest_distances) = minimize_graph(&graph, &shortest_distances);
+
+    // Without elephant.
+    {
+        let best_flow = find_max_flow_without_elephant(&min_graph, &min_shortest_distances);
+        println!("(test) best flow without elephant: {}", best_flow);
+        assert_eq!(best_flow, 1651);
+    }
+
+    // With elephant.
+    {
+        let best_flow = find_max_flow_with_elephant(&min_graph, &min_shortest_distances);
+        println!("(test) best flow with elephant: {}", best_flow);
+        assert_eq!(best_flow, 1707);
+    }
+}
+
+#[cfg(test)]
+fn check_plan(plan: &Plan<'_>, graph: &ValveGraph<'_>, agents: usize, minutes: u32) {
+    assert_eq!(plan.schedules.len(), agents);
+
+    let mut opened = vec![];
+    let mut total = 0;
+    for schedule in &plan.schedules {
+        let mut minute = 0;
+        for opening in schedule {
+            assert!(opening.minute > minute && opening.minute < minutes);
+            minute = opening.minute;
+
+            let valve = graph.name_to_valve(opening.valve).unwrap();
+            let rate = graph.valves[valve.0].flow_rate.0;
+            assert_eq!(opening.released, rate * (minutes - opening.minute));
+            total += opening.released;
+            opened.push(opening.valve);
+        }
+    }
+    assert_eq!(total, plan.total_flow);
+
+    let count = opened.len();
+    opened.sort();
+    opened.dedup();
+    assert_eq!(opened.len(), count, "a valve opened twice");
+}
+
+#[test]
+fn plans() {
+    let input = include_str!("../problem.txt")
+        .lines()
+        .skip_while(|line| !line.starts_with("Valve AA"))
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let volcano = Day16::parse(&input).unwrap();
+    let (graph, shortest_distances) = (&volcano.min_graph, &volcano.min_shortest_distances);
+
+    let opening = |minute, valve, released| Opening {
+        minute,
+        valve,
+        released,
+    };
+
+    let plan = best_plan(graph, shortest_distances, 1, 30);
+    check_plan(&plan, graph, 1, 30);
+    assert!(volcano.narrate(1, 30).ends_with(
+        "== Minute 30 ==
+Valves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure.
+
+Total pressure released: 1651
+"
+    ));
+    assert_eq!(
+        plan,
+        Plan {
+            total_flow: 1651,
+            schedules: vec![vec![
+                opening(2, "DD", 560),
+                opening(5, "BB", 325),
+                opening(9, "JJ", 441),
+                opening(17, "HH", 286),
+                opening(21, "EE", 27),
+                opening(24, "CC", 12),
+            ]],
+        }
+    );
+
+    let mut plan = best_plan(graph, shortest_distances, 2, 26);
+    check_plan(&plan, graph, 2, 26);
+    plan.schedules.sort_by_key(|schedule| schedule[0].valve);
+    assert_eq!(
+        plan,
+        Plan {
+            total_flow: 1707,
+            schedules: vec![
+                vec![
+                    opening(2, "DD", 480),
+                    opening(7, "HH", 418),
+                    opening(11, "EE", 45),
+                ],
+                vec![
+                    opening(3, "JJ", 483),
+                    opening(7, "BB", 247),
+                    opening(9, "CC", 34),
+                ],
+            ],
+        }
+    );
+
+    // The narrative, with you opening JJ first, starts as in the puzzle.
+    plan.schedules.reverse();
+    let narrative = plan.narrative(&volcano.graph, &volcano.shortest_distances, 26);
+    let lines = include_str!("../problem.txt")
+        .lines()
+        .skip_while(|line| !line.starts_with("In the example above, you could teach"))
+        .skip(2)
+        .take_while(|line| !line.starts_with("(At this point"))
+        .collect::<Vec<_>>();
+    assert_eq!(lines[0], "== Minute 1 ==");
+    assert!(narrative.starts_with(&lines.join("\n")));
+
+    // More agents can't do worse than fewer with the same time.
+    let mut previous = 0;
+    for agents in 1..=4 {
+        let plan = best_plan(graph, shortest_distances, agents, 22);
+        check_plan(&plan, graph, agents, 22);
+        assert!(plan.total_flow >= previous);
+        previous = plan.total_flow;
+    }
+}
+
+#[test]
+fn strategies() {
+    for seed in 0..3 {
+        let scan = generate_scan(12, seed);
+        let volcano = Day16::parse(&scan).unwrap();
+        for (agents, minutes) in [(1, 30), (2, 26), (3, 20)] {
+            let subsets = volcano.plan_by(agents, minutes, Strategy::Subsets);
+            let bnb = volcano.plan_by(agents, minutes, Strategy::BranchAndBound);
+            check_plan(&subsets, &volcano.min_graph, agents, minutes);
+            check_plan(&bnb, &volcano.min_graph, agents, minutes);
+            assert_eq!(subsets.total_flow, bnb.total_flow);
+        }
+    }
+
+    // Far too many valves to try every subset of.
+    let scan = generate_scan(60, 0);
+    let volcano = Day16::parse(&scan).unwrap();
+    assert_eq!(volcano.min_graph.valve_count(), 61);
+    let plan = volcano.plan_by(1, 30, Strategy::BranchAndBound);
+    check_plan(&plan, &volcano.min_graph, 1, 30);
+    let subsets = volcano.plan_by(1, 30, Strategy::Subsets);
+    assert_eq!(subsets.total_flow, plan.total_flow);
+}
+
+#[test]
+fn unreachable_valves() {
+    // CC has the most flow, but no tunnel leads to it.
+    let scan = "Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=5; tunnel leads to valve AA
+Valve CC has flow rate=7; tunnel leads to valve CC";
+    let volcano = Day16::parse(scan).unwrap();
+    for strategy in [Strategy::Subsets, Strategy::BranchAndBound] {
+        for (agents, minutes, total_flow) in [(1, 30, 140), (2, 26, 120)] {
+            let plan = volcano.plan_by(agents, minutes, strategy);
+            check_plan(&plan, &volcano.min_graph, agents, minutes);
+            assert_eq!(plan.total_flow, total_flow, "{strategy:?}");
+        }
+    }
+    assert_eq!(Day16::part1(&volcano), "140");
+    assert_eq!(Day16::part2(&volcano), "120");
+}
+
+/// The valves, and the same valves cut down to the ones worth opening, which
+/// are all that matter for finding the most pressure to release.
+pub struct Volcano<'a> {
+    graph: ValveGraph<'a>,
+    shortest_distances: ShortestDistanceInfo,
+    min_graph: ValveGraph<'a>,
+    min_shortest_distances: ShortestDistanceInfo,
+}
+
+impl<'a> Volcano<'a> {
+    /// The best plan for `agents` agents with `minutes` minutes, found using
+    /// `strategy`.
+    pub fn plan_by(&self, agents: usize, minutes: u32, strategy: Strategy) -> Plan<'a> {
+        best_plan_by(
+            &self.min_graph,
+            &self.min_shortest_distances,
+            agents,
+            minutes,
+            strategy,
+        )
+    }
+
+    /// The story of how `agents` agents release the most pressure in
+    /// `minutes` minutes.
+    pub fn narrate(&self, agents: usize, minutes: u32) -> String {
+        let plan = best_plan(
+            &self.min_graph,
+            &self.min_shortest_distances,
+            agents,
+            minutes,
+        );
+        let mut s = plan.narrative(&self.graph, &self.shortest_distances, minutes);
+        writeln!(s, "Total pressure released: {}", plan.total_flow).unwrap();
+        s
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 16;
+
+    type Parsed<'a> = Volcano<'a>;
+
+    fn parse(input: &str) -> Result<Volcano<'_>, ParseError> {
+        let graph = build_valve_graph(input)?;
+
+        let shortest_distances = ShortestDistanceInfo::new(&graph);
+
+        let (min_graph, min_shortest_distances) = minimize_graph(&graph, &shortest_distances);
+        Ok(Volcano {
+            graph,
+            shortest_distances,
+            min_graph,
+            min_shortest_distances,
+        })
+    }
+
+    fn part1(volcano: &Volcano<'_>) -> String {
+        find_max_flow_without_elephant(&volcano.min_graph, &volcano.min_shortest_distances)
+            .to_string()
+    }
+
+    fn part2(volcano: &Volcano<'_>) -> String {
+        find_max_flow_with_elephant(&volcano.min_graph, &volcano.min_shortest_distances).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day16);
@@ -0,0 +1,609 @@
+use aoc_common::cycle::Fingerprints;
+use aoc_common::{ParseError, Solution};
+
+#[derive(Copy, Clone)]
+pub enum Push {
+    Left,
+    Right,
+}
+
+fn to_jet_pattern(input: &str) -> Result<Vec<Push>, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::new(input, "a jet pattern"));
+    }
+
+    input
+        .char_indices()
+        .map(|(i, c)| match c {
+            '>' => Ok(Push::Right),
+            '<' => Ok(Push::Left),
+            c => Err(ParseError::new(&input[i..i + c.len_utf8()], "'<' or '>'")),
+        })
+        .collect()
+}
+
+/// A rock, encoded as its layers from bottom to top, smashed against the
+/// right wall: bit 0 of each layer is the rock's rightmost column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rock {
+    layers: Vec<u64>,
+    /// The width of the rock at its widest.
+    width: usize,
+}
+
+/// The rocks in the puzzle, in the order they fall.
+pub const PUZZLE_ROCKS: &str = include_str!("../rocks.txt");
+
+/// Parse rocks drawn as in the puzzle, `#` for rock and `.` for empty space,
+/// with a blank line after each rock:
+///
+/// ```text
+/// .#.
+/// ###
+/// .#.
+///
+/// ##
+/// ##
+/// ```
+pub fn parse_rocks(text: &str) -> Result<Vec<Rock>, ParseError> {
+    let mut rocks = vec![];
+    let mut lines = text.lines().peekable();
+    while lines.peek().is_some() {
+        let rows = lines
+            .by_ref()
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            continue;
+        }
+        rocks.push(parse_rock(&rows)?);
+    }
+    Ok(rocks)
+}
+
+fn parse_rock(rows: &[&str]) -> Result<Rock, ParseError> {
+    let (mut left, mut right) = (usize::MAX, 0);
+    for row in rows {
+        if let Some((i, c)) = row.char_indices().find(|&(_, c)| c != '#' && c != '.') {
+            return Err(ParseError::new(&row[i..i + c.len_utf8()], "'#' or '.'"));
+        }
+        let (Some(first), Some(last)) = (row.find('#'), row.rfind('#')) else {
+            return Err(ParseError::new(row, "a row with some rock in it"));
+        };
+        left = left.min(first);
+        right = right.max(last);
+    }
+
+    let width = right - left + 1;
+    if width > Chamber::MAX_WIDTH {
+        return Err(ParseError::new(
+            rows[0],
+            format!("a rock at most {} wide", Chamber::MAX_WIDTH),
+        ));
+    }
+
+    let layers = rows
+        .iter()
+        .rev()
+        .map(|row| {
+            row.bytes()
+                .enumerate()
+                .filter(|&(_, b)| b == b'#')
+                .fold(0, |layer, (i, _)| layer | 1 << (right - i))
+        })
+        .collect();
+    Ok(Rock { layers, width })
+}
+
+struct Chamber {
+    width: usize,
+    /// The layers of the tower still within reach of falling rocks, from
+    /// bottom to top.
+    layers: Vec<u64>,
+    /// How many layers at the bottom of the tower have been discarded.
+    trimmed: u64,
+    tower_height: u64,
+}
+
+impl Chamber {
+    const MAX_WIDTH: usize = u64::BITS as usize;
+    const LAYERS_ABOVE: usize = 3;
+    const GAP_LEFT: usize = 2;
+    /// How far down from the top of the tower `trim` looks for a layer rocks
+    /// can't get past, so that it takes the same time however many layers
+    /// are kept.
+    const TRIM_DEPTH: usize = 128;
+
+    fn new(width: usize) -> Chamber {
+        Chamber {
+            width,
+            layers: vec![],
+            trimmed: 0,
+            tower_height: 0,
+        }
+    }
+
+    /// Discard the layers no falling rock can reach any more: those below the
+    /// lowest layer with an empty space reachable from above the tower by
+    /// moving only down, left and right, the ways rocks move.  Nothing is
+    /// discarded unless rocks are walled off within `TRIM_DEPTH` layers of
+    /// the top.
+    fn trim(&mut self) {
+        let all = u64::MAX >> (Chamber::MAX_WIDTH - self.width);
+
+        let mut reachable = all;
+        let mut lowest = self.layers.len();
+        let top_layers = self.layers.iter().enumerate().rev();
+        for (idx, layer) in top_layers.take(Chamber::TRIM_DEPTH) {
+            let empty = !layer & all;
+            reachable &= empty;
+            loop {
+                let spread = (reachable | reachable << 1 | reachable >> 1) & empty;
+                if spread == reachable {
+                    break;
+                }
+                reachable = spread;
+            }
+
+            if reachable == 0 {
+                break;
+            }
+            lowest = idx;
+        }
+        if reachable != 0 {
+            return;
+        }
+
+        self.layers.drain(..lowest);
+        self.trimmed += lowest as u64;
+    }
+
+    fn row_text(&self, layer: u64) -> String {
+        let mut line = String::from("|");
+        for column in (0..self.width).rev() {
+            line.push(if layer & (1 << column) != 0 { '#' } else { '.' });
+        }
+        line.push('|');
+        line
+    }
+
+    fn floor_text(&self) -> String {
+        format!("+{}+", "-".repeat(self.width))
+    }
+}
+
+fn rock_overlaps_tower(
+    rock: &[u64],
+    rock_bottom_idx: usize,
+    rock_offset: usize,
+    chamber: &Chamber,
+) -> bool {
+    let chamber_layers = &chamber.layers;
+    if rock_bottom_idx >= chamber_layers.len() {
+        return false;
+    }
+
+    rock.iter()
+        .zip(&chamber_layers[rock_bottom_idx..])
+        .any(|(rock_layer, chamber_layer)| chamber_layer & (rock_layer << rock_offset) != 0)
+}
+
+const DEBUG: bool = false;
+
+#[allow(dead_code)]
+fn dump_chamber(desc: &str, i: usize, chamber: &Chamber) {
+    if !DEBUG || i >= 5 {
+        return;
+    }
+
+    println!("{desc}");
+
+    let mut out = vec![chamber.floor_text()];
+    for layer in &chamber.layers {
+        out.push(chamber.row_text(*layer));
+    }
+    for _ in 0..Chamber::LAYERS_ABOVE {
+        out.push(chamber.row_text(0));
+    }
+
+    out.reverse();
+    println!("{}\n", out.join("\n"));
+}
+
+#[allow(dead_code)]
+fn dump_chamber_and_falling_rock(
+    desc: &str,
+    i: usize,
+    chamber: &Chamber,
+    rock: &[u64],
+    rock_bottom_idx: usize,
+    rock_offset: usize,
+) {
+    if !DEBUG || i >= 5 {
+        return;
+    }
+
+    println!("{desc}");
+
+    let mut out = vec![chamber.floor_text()];
+    for (i, layer) in chamber
+        .layers
+        .iter()
+        .chain(std::iter::repeat_n(&0, Chamber::LAYERS_ABOVE))
+        .enumerate()
+    {
+        let rock_contrib = if rock_bottom_idx <= i && i < rock_bottom_idx + rock.len() {
+            rock[i - rock_bottom_idx] << rock_offset
+        } else {
+            0
+        };
+
+        out.push(chamber.row_text(*layer | rock_contrib));
+    }
+
+    out.reverse();
+    println!("{}\n", out.join("\n"));
+}
+
+fn run(
+    chamber: &mut Chamber,
+    rocks: &[Rock],
+    jet_pattern: &[Push],
+    num_rocks: usize,
+    (mut rock_idx, mut jet_idx): (usize, usize),
+) -> (usize, usize) {
+    for i in 0..num_rocks {
+        let Rock { layers, width } = &rocks[rock_idx];
+        let (rock, rock_width) = (&layers[..], *width);
+        rock_idx = (rock_idx + 1) % rocks.len();
+
+        let rock_starting_offset = chamber.width - Chamber::GAP_LEFT - rock_width;
+        let mut rock_bottom_idx = chamber.layers.len() + Chamber::LAYERS_ABOVE;
+        let mut rock_offset = rock_starting_offset;
+
+        loop {
+            let jet = jet_pattern[jet_idx];
+            jet_idx = (jet_idx + 1) % jet_pattern.len();
+
+            dump_chamber_and_falling_rock(
+                "before pushing",
+                i,
+                chamber,
+                rock,
+                rock_bottom_idx,
+                rock_offset,
+            );
+
+            // Push rock.
+            let cand_rock_offset = match jet {
+                Push::Left => rock_offset + 1,
+                Push::Right => rock_offset.saturating_sub(1),
+            };
+            if cand_rock_offset != rock_offset
+                && cand_rock_offset + rock_width <= chamber.width
+                && !rock_overlaps_tower(rock, rock_bottom_idx, cand_rock_offset, chamber)
+            {
+                rock_offset = cand_rock_offset;
+            }
+
+            dump_chamber_and_falling_rock(
+                "after pushing",
+                i,
+                chamber,
+                rock,
+                rock_bottom_idx,
+                rock_offset,
+            );
+
+            // Rock falls.
+            if rock_bottom_idx == 0 {
+                break;
+            }
+
+            if rock_overlaps_tower(rock, rock_bottom_idx - 1, rock_offset, chamber) {
+                break;
+            }
+
+            rock_bottom_idx -= 1;
+        }
+
+        // Apply rock from bottom up, then pad with empty layers.
+        for (i, rock_layer) in rock.iter().enumerate() {
+            let chamber_layer_idx = rock_bottom_idx + i;
+            if chamber_layer_idx == chamber.layers.len() {
+                chamber.layers.push(0);
+                chamber.tower_height += 1;
+            }
+
+            let chamber_layer = &mut chamber.layers[chamber_layer_idx];
+            let rock = *rock_layer << rock_offset;
+            *chamber_layer |= rock;
+        }
+
+        dump_chamber("after placement:", i, chamber);
+
+        chamber.trim();
+    }
+
+    (rock_idx, jet_idx)
+}
+
+/// The tower after some rocks have fallen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tower {
+    pub height: u64,
+    /// How many layers at the bottom of the tower were discarded as out of
+    /// reach of falling rocks.
+    pub trimmed: u64,
+}
+
+/// A chamber of some width, and the rocks that fall into it.
+pub struct Simulator {
+    rocks: Vec<Rock>,
+    width: usize,
+}
+
+impl Simulator {
+    /// The widest chamber that can be simulated.
+    pub const MAX_WIDTH: usize = Chamber::MAX_WIDTH;
+
+    /// A simulator of `rocks` falling in turn into a chamber `width` wide.
+    /// Each rock must fit in the chamber when it appears, two units from
+    /// its left wall.
+    pub fn new(rocks: Vec<Rock>, width: usize) -> Result<Simulator, String> {
+        if rocks.is_empty() {
+            return Err("no rocks to drop".to_string());
+        }
+        if width > Simulator::MAX_WIDTH {
+            return Err(format!(
+                "chamber {width} wide is wider than {}",
+                Simulator::MAX_WIDTH
+            ));
+        }
+        if let Some((i, rock)) = rocks
+            .iter()
+            .enumerate()
+            .find(|(_, rock)| Chamber::GAP_LEFT + rock.width > width)
+        {
+            return Err(format!(
+                "rock {} is {} wide, too wide to fall into a chamber {width} wide",
+                i + 1,
+                rock.width
+            ));
+        }
+
+        Ok(Simulator { rocks, width })
+    }
+
+    /// The puzzle's rocks falling into a chamber seven units wide.
+    pub fn puzzle() -> Simulator {
+        let rocks = parse_rocks(PUZZLE_ROCKS).expect("puzzle rocks");
+        Simulator::new(rocks, 7).expect("puzzle chamber")
+    }
+
+    /// The tower after `num_rocks` rocks have fallen, pushed around by the
+    /// jets in `jet_pattern`, dropping every one of them.  Only the layers
+    /// rocks can still reach are kept, so as long as the rocks keep walling
+    /// off the layers below them, this needs little memory however many
+    /// rocks fall.
+    pub fn drop_rocks(&self, jet_pattern: &[Push], num_rocks: u64) -> Tower {
+        let mut chamber = Chamber::new(self.width);
+        let num_rocks = usize::try_from(num_rocks).expect("rock count fits in usize");
+        run(&mut chamber, &self.rocks, jet_pattern, num_rocks, (0, 0));
+        Tower {
+            height: chamber.tower_height,
+            trimmed: chamber.trimmed,
+        }
+    }
+
+    /// The tower after `num_rocks` rocks have fallen, pushed around by the
+    /// jets in `jet_pattern`, skipping ahead once the tower starts growing
+    /// the same way over and over.  That only happens if the rocks keep
+    /// walling off the layers below them; otherwise every rock is dropped.
+    pub fn tower(&self, jet_pattern: &[Push], num_rocks: u64) -> Tower {
+        let mut chamber = Chamber::new(self.width);
+
+        // Drop rocks until the rock and jet patterns are where they were
+        // after some earlier rock, atop the same layers within reach.  From
+        // then on the tower grows the same way over and over.
+        let (mut rock_idx, mut jet_idx) = (0, 0);
+        let mut heights = vec![0];
+        let mut kept = vec![0];
+        let mut fingerprints = Fingerprints::new();
+        fingerprints.record((rock_idx, jet_idx, chamber.layers.clone()));
+        let cycle = loop {
+            if heights.len() as u64 > num_rocks {
+                return Tower {
+                    height: chamber.tower_height,
+                    trimmed: chamber.trimmed,
+                };
+            }
+
+            (rock_idx, jet_idx) = run(
+                &mut chamber,
+                &self.rocks,
+                jet_pattern,
+                1,
+                (rock_idx, jet_idx),
+            );
+            heights.push(chamber.tower_height as i64);
+            kept.push(chamber.layers.len());
+
+            let fingerprint = (rock_idx, jet_idx, chamber.layers.clone());
+            if let Some(cycle) = fingerprints.record(fingerprint) {
+                break cycle;
+            }
+        };
+
+        // The layers kept are the same each time around the cycle, so below
+        // them, the tower's all trimmed.
+        let height = cycle.extrapolate(&heights, num_rocks) as u64;
+        let (step, _) = cycle.reduce(num_rocks);
+        Tower {
+            height,
+            trimmed: height - kept[step] as u64,
+        }
+    }
+
+    /// How tall the tower is after `num_rocks` rocks have fallen, pushed
+    /// around by the jets in `jet_pattern`.
+    pub fn tower_height(&self, jet_pattern: &[Push], num_rocks: u64) -> u64 {
+        self.tower(jet_pattern, num_rocks).height
+    }
+}
+
+fn part1(jet_pattern: &[Push]) -> u64 {
+    let simulator = Simulator::puzzle();
+
+    const NUM_ROCKS: usize = 2022;
+
+    let mut chamber = Chamber::new(simulator.width);
+    let (next_rock_idx, _next_jet_idx) = run(
+        &mut chamber,
+        &simulator.rocks,
+        jet_pattern,
+        NUM_ROCKS,
+        (0, 0),
+    );
+    assert_eq!(next_rock_idx, NUM_ROCKS % simulator.rocks.len());
+
+    chamber.tower_height
+}
+
+fn part2(jet_pattern: &[Push]) -> u64 {
+    const GAZILLION_ROCKS_DROPPED_COUNT: u64 = 1_000_000_000_000;
+
+    Simulator::puzzle().tower_height(jet_pattern, GAZILLION_ROCKS_DROPPED_COUNT)
+}
+
+#[test]
+fn test_example() {
+    static INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+    let jet_pattern = to_jet_pattern(INPUT).unwrap();
+
+    assert_eq!(part1(&jet_pattern), 3068);
+    assert_eq!(part2(&jet_pattern), 1_514_285_714_288);
+
+    assert!(Day17::parse("\n").is_err());
+}
+
+#[test]
+fn trims_unreachable_layers() {
+    // Only the layers above a row rocks can't get past are kept...
+    let mut chamber = Chamber::new(7);
+    chamber.layers = vec![0b0000011, 0b1111100, 0b0000001, 0b1000000];
+    chamber.trim();
+    assert_eq!(chamber.layers, [0b1111100, 0b0000001, 0b1000000]);
+    assert_eq!(chamber.trimmed, 1);
+
+    // ...even where getting past it means moving sideways first...
+    chamber.layers = vec![0b0000000, 0b0011111, 0b1000000, 0b0000010];
+    chamber.trim();
+    assert_eq!(chamber.layers.len(), 4);
+
+    // ...or where that row is the top of the tower.
+    chamber.layers = vec![0b0001000, 0b1111111];
+    chamber.trim();
+    assert!(chamber.layers.is_empty());
+    assert_eq!(chamber.trimmed, 3);
+
+    // Rows rocks can't get past are only looked for so far down.
+    let mut deep = vec![0b1111111];
+    deep.extend([0b1111110; Chamber::TRIM_DEPTH]);
+    chamber.layers = deep.clone();
+    chamber.trim();
+    assert_eq!(chamber.layers, deep);
+    chamber.layers.push(0b1111111);
+    chamber.trim();
+    assert!(chamber.layers.is_empty());
+
+    // Far more rocks than the cycle-finding shortcut needs, all dropped
+    // keeping only a few layers at a time.
+    let jet_pattern = to_jet_pattern(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>").unwrap();
+    let simulator = Simulator::puzzle();
+    let tower = simulator.drop_rocks(&jet_pattern, 100_000);
+    assert_eq!(tower, simulator.tower(&jet_pattern, 100_000));
+    assert!(tower.height - tower.trimmed < 100);
+
+    // The same in the widest chamber, with a plank nearly as wide falling
+    // now and then to wall off what's below.
+    let mut rocks = parse_rocks(PUZZLE_ROCKS).unwrap();
+    rocks.extend(parse_rocks(&"#".repeat(60)).unwrap());
+    let simulator = Simulator::new(rocks, Simulator::MAX_WIDTH).unwrap();
+    let tower = simulator.drop_rocks(&jet_pattern, 100_000);
+    assert_eq!(tower, simulator.tower(&jet_pattern, 100_000));
+    assert!(tower.height - tower.trimmed < 100);
+}
+
+#[test]
+fn custom_rocks() {
+    static ROCKS: &str = "\
+###
+.#.
+
+.##
+##.
+
+#.
+#.
+##
+
+####
+
+##
+##
+";
+    let rocks = parse_rocks(ROCKS).unwrap();
+    assert_eq!(rocks.len(), 5);
+    assert_eq!(rocks[0].layers, [0b010, 0b111]);
+    assert_eq!(rocks[2].layers, [0b11, 0b10, 0b10]);
+
+    // The cycle-finding shortcut agrees with dropping every rock, in a
+    // chamber as wide as can be simulated.
+    let jet_pattern = to_jet_pattern("<<><>>><<<<>><<>>>>><<><<<><<>>><><>>>>>><<<>><>").unwrap();
+    let simulator = Simulator::new(rocks, Simulator::MAX_WIDTH).unwrap();
+    for num_rocks in [0, 1, 5, 1000, 4321] {
+        let mut chamber = Chamber::new(simulator.width);
+        run(
+            &mut chamber,
+            &simulator.rocks,
+            &jet_pattern,
+            num_rocks,
+            (0, 0),
+        );
+        assert_eq!(
+            simulator.tower_height(&jet_pattern, num_rocks as u64),
+            chamber.tower_height
+        );
+    }
+
+    assert!(parse_rocks("#x#").is_err());
+    assert!(parse_rocks("##\n..\n##").is_err());
+    assert!(parse_rocks(&"#".repeat(65)).is_err());
+    let too_wide = parse_rocks(&"#".repeat(63)).unwrap();
+    assert!(Simulator::new(too_wide, 64).is_err());
+    assert!(Simulator::new(vec![], 7).is_err());
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 17;
+
+    type Parsed<'a> = Vec<Push>;
+
+    fn parse(input: &str) -> Result<Vec<Push>, ParseError> {
+        to_jet_pattern(input.trim())
+    }
+
+    fn part1(jet_pattern: &Vec<Push>) -> String {
+        part1(jet_pattern).to_string()
+    }
+
+    fn part2(jet_pattern: &Vec<Push>) -> String {
+        part2(jet_pattern).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day17);
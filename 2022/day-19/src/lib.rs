@@ -0,0 +1,963 @@
+use aoc_common::{parse, ParseError, Solution};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+type ResourceCount = u16;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+struct Resources {
+    ore: ResourceCount,
+    clay: ResourceCount,
+    obsidian: ResourceCount,
+    geode: ResourceCount,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u16,
+    ore_robot_cost: Resources,
+    clay_robot_cost: Resources,
+    obsidian_robot_cost: Resources,
+    geode_robot_cost: Resources,
+}
+
+/// Consume the space-separated `expected` words from `words`, which are split
+/// from the blueprint `s`.
+fn expect_words<'a>(
+    words: &mut impl Iterator<Item = &'a str>,
+    s: &str,
+    expected: &str,
+) -> Result<(), ParseError> {
+    for want in expected.split(' ') {
+        let word = parse::next(words, s, expected)?;
+        if word != want {
+            return Err(ParseError::new(word, format!("{want:?}")));
+        }
+    }
+    Ok(())
+}
+
+fn cost<'a>(
+    words: &mut impl Iterator<Item = &'a str>,
+    s: &str,
+) -> Result<ResourceCount, ParseError> {
+    parse::value(parse::next(words, s, "a cost")?, "a cost")
+}
+
+fn parse_blueprint(s: &str) -> Result<Blueprint, ParseError> {
+    // Blueprints may be wrapped over several lines, so only the words matter.
+    let mut words = s.split_whitespace();
+    let words = &mut words;
+
+    // "Blueprint 10: "
+    expect_words(words, s, "Blueprint")?;
+    let id = parse::next(words, s, "a blueprint number")?;
+    let id = parse::value(parse::strip_suffix(id, ":")?, "a blueprint number")?;
+
+    // "Each ore robot costs 4 ore. "
+    expect_words(words, s, "Each ore robot costs")?;
+    let ore_robot_cost = Resources {
+        ore: cost(words, s)?,
+        clay: 0,
+        obsidian: 0,
+        geode: 0,
+    };
+    expect_words(words, s, "ore.")?;
+
+    // "Each clay robot costs 4 ore. "
+    expect_words(words, s, "Each clay robot costs")?;
+    let clay_robot_cost = Resources {
+        ore: cost(words, s)?,
+        clay: 0,
+        obsidian: 0,
+        geode: 0,
+    };
+    expect_words(words, s, "ore.")?;
+
+    // "Each obsidian robot costs 4 ore and 20 clay. "
+    expect_words(words, s, "Each obsidian robot costs")?;
+    let ore = cost(words, s)?;
+    expect_words(words, s, "ore and")?;
+    let clay = cost(words, s)?;
+    expect_words(words, s, "clay.")?;
+    let obsidian_robot_cost = Resources {
+        ore,
+        clay,
+        obsidian: 0,
+        geode: 0,
+    };
+
+    // "Each geode robot costs 2 ore and 12 obsidian."
+    expect_words(words, s, "Each geode robot costs")?;
+    let ore = cost(words, s)?;
+    expect_words(words, s, "ore and")?;
+    let obsidian = cost(words, s)?;
+    expect_words(words, s, "obsidian.")?;
+    let geode_robot_cost = Resources {
+        ore,
+        clay: 0,
+        obsidian,
+        geode: 0,
+    };
+
+    if let Some(word) = words.next() {
+        return Err(ParseError::new(word, "the end of the blueprint"));
+    }
+
+    Ok(Blueprint {
+        id,
+        ore_robot_cost,
+        clay_robot_cost,
+        obsidian_robot_cost,
+        geode_robot_cost,
+    })
+}
+
+fn parse_input(input: &str, splitter: &str) -> Result<Vec<Blueprint>, ParseError> {
+    input.trim().split(splitter).map(parse_blueprint).collect()
+}
+
+impl Resources {
+    fn withdraw(&mut self, amount: &Resources) -> bool {
+        if self.ore >= amount.ore
+            && self.clay >= amount.clay
+            && self.obsidian >= amount.obsidian
+            && self.geode >= amount.geode
+        {
+            self.ore -= amount.ore;
+            self.clay -= amount.clay;
+            self.obsidian -= amount.obsidian;
+            self.geode -= amount.geode;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+type RobotCount = u16;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+struct Robots {
+    ore: RobotCount,
+    clay: RobotCount,
+    obsidian: RobotCount,
+    geode: RobotCount,
+}
+
+/// A kind of robot.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Robot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+/// Robots to try building, most promising first.
+const ROBOTS: [Robot; 4] = [Robot::Geode, Robot::Obsidian, Robot::Clay, Robot::Ore];
+
+impl Blueprint {
+    fn cost(&self, robot: Robot) -> &Resources {
+        match robot {
+            Robot::Ore => &self.ore_robot_cost,
+            Robot::Clay => &self.clay_robot_cost,
+            Robot::Obsidian => &self.obsidian_robot_cost,
+            Robot::Geode => &self.geode_robot_cost,
+        }
+    }
+
+    /// The most of each resource any robot costs.  Only so much can be spent
+    /// each minute, so there's no point having more robots collecting it.
+    fn most_spent(&self) -> Resources {
+        let costs = ROBOTS.map(|robot| *self.cost(robot));
+        Resources {
+            ore: costs.iter().map(|cost| cost.ore).max().unwrap_or(0),
+            clay: costs.iter().map(|cost| cost.clay).max().unwrap_or(0),
+            obsidian: costs.iter().map(|cost| cost.obsidian).max().unwrap_or(0),
+            geode: ResourceCount::MAX,
+        }
+    }
+}
+
+impl Robot {
+    /// What the robot does, as the puzzle puts it.
+    fn kind(self) -> &'static str {
+        match self {
+            Robot::Ore => "ore-collecting",
+            Robot::Clay => "clay-collecting",
+            Robot::Obsidian => "obsidian-collecting",
+            Robot::Geode => "geode-cracking",
+        }
+    }
+
+    fn article(self) -> &'static str {
+        match self {
+            Robot::Ore | Robot::Obsidian => "an",
+            Robot::Clay | Robot::Geode => "a",
+        }
+    }
+}
+
+impl Resources {
+    /// How much there is of what `robot` collects.
+    fn collected_by(&self, robot: Robot) -> ResourceCount {
+        match robot {
+            Robot::Ore => self.ore,
+            Robot::Clay => self.clay,
+            Robot::Obsidian => self.obsidian,
+            Robot::Geode => self.geode,
+        }
+    }
+}
+
+impl Robots {
+    fn count(&self, robot: Robot) -> RobotCount {
+        match robot {
+            Robot::Ore => self.ore,
+            Robot::Clay => self.clay,
+            Robot::Obsidian => self.obsidian,
+            Robot::Geode => self.geode,
+        }
+    }
+
+    fn add(&mut self, robot: Robot) {
+        match robot {
+            Robot::Ore => self.ore += 1,
+            Robot::Clay => self.clay += 1,
+            Robot::Obsidian => self.obsidian += 1,
+            Robot::Geode => self.geode += 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+struct State {
+    time: u8,
+    resources: Resources,
+    robots: Robots,
+}
+
+impl State {
+    /// No resources, and one ore-collecting robot.
+    const START: State = State {
+        time: 0,
+        resources: Resources {
+            ore: 0,
+            clay: 0,
+            obsidian: 0,
+            geode: 0,
+        },
+        robots: Robots {
+            ore: 1,
+            clay: 0,
+            obsidian: 0,
+            geode: 0,
+        },
+    };
+
+    fn minutes(&mut self, count: u8) {
+        self.time += count;
+
+        self.resources.ore += count as u16 * self.robots.ore;
+        self.resources.clay += count as u16 * self.robots.clay;
+        self.resources.obsidian += count as u16 * self.robots.obsidian;
+        self.resources.geode += count as u16 * self.robots.geode;
+    }
+
+    /// Spend a minute building `robot`, which must be affordable.
+    fn build(&mut self, robot: Robot, blueprint: &Blueprint) {
+        assert!(
+            self.resources.withdraw(blueprint.cost(robot)),
+            "enough resources to build a robot"
+        );
+        self.minutes(1);
+        self.robots.add(robot);
+    }
+
+    fn advance_to_end(&self, time_limit: u8) -> ResourceCount {
+        let mut state = *self;
+        state.minutes(time_limit - state.time);
+        state.resources.geode
+    }
+
+    /// Wait until `robot` can be built, then build it, if that leaves time
+    /// for it to collect anything.
+    fn buy_robot(&self, robot: Robot, blueprint: &Blueprint, time_limit: u8) -> Option<State> {
+        let cost = blueprint.cost(robot);
+
+        // The minutes to wait to have enough of each resource.
+        let wait = |have: ResourceCount, need: ResourceCount, robots: RobotCount| {
+            if have >= need {
+                Some(0)
+            } else if robots == 0 {
+                None
+            } else {
+                Some((need - have).div_ceil(robots))
+            }
+        };
+        let wait = [
+            wait(self.resources.ore, cost.ore, self.robots.ore)?,
+            wait(self.resources.clay, cost.clay, self.robots.clay)?,
+            wait(self.resources.obsidian, cost.obsidian, self.robots.obsidian)?,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+
+        // The robot takes a minute to build, and must have a minute left after
+        // that to collect anything.
+        if u16::from(self.time) + wait + 2 > u16::from(time_limit) {
+            return None;
+        }
+
+        let mut state = *self;
+        state.minutes(wait as u8);
+        state.build(robot, blueprint);
+        Some(state)
+    }
+
+    /// Whether another `robot` could collect anything that could be spent.
+    fn worth_buying(&self, robot: Robot, most_spent: &Resources, time_limit: u8) -> bool {
+        let (robots, have, most) = match robot {
+            Robot::Ore => (self.robots.ore, self.resources.ore, most_spent.ore),
+            Robot::Clay => (self.robots.clay, self.resources.clay, most_spent.clay),
+            Robot::Obsidian => (
+                self.robots.obsidian,
+                self.resources.obsidian,
+                most_spent.obsidian,
+            ),
+            Robot::Geode => return true,
+        };
+
+        // Enough robots, and enough of the resource already, to spend as much
+        // as possible every remaining minute?
+        let remaining = u16::from(time_limit - self.time);
+        robots < most
+            && u32::from(have) + u32::from(robots) * u32::from(remaining)
+                < u32::from(most) * u32::from(remaining)
+    }
+
+    /// The most geodes there could be at the end, if ore were free and a clay
+    /// robot were built every minute, and an obsidian robot and a geode robot
+    /// every minute there were clay and obsidian enough for them.
+    fn max_geodes_achievable(&self, blueprint: &Blueprint, time_limit: u8) -> ResourceCount {
+        let (mut clay, mut obsidian, mut geodes) = (
+            self.resources.clay,
+            self.resources.obsidian,
+            self.resources.geode,
+        );
+        let mut robots = self.robots;
+        for _ in self.time..time_limit {
+            let obsidian_robot = clay >= blueprint.obsidian_robot_cost.clay;
+            let geode_robot = obsidian >= blueprint.geode_robot_cost.obsidian;
+            if obsidian_robot {
+                clay -= blueprint.obsidian_robot_cost.clay;
+            }
+            if geode_robot {
+                obsidian -= blueprint.geode_robot_cost.obsidian;
+            }
+
+            clay += robots.clay;
+            obsidian += robots.obsidian;
+            geodes += robots.geode;
+
+            robots.clay += 1;
+            robots.obsidian += u16::from(obsidian_robot);
+            robots.geode += u16::from(geode_robot);
+        }
+        geodes
+    }
+}
+
+impl std::fmt::Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let t = self.time;
+
+        let Resources {
+            ore,
+            clay,
+            obsidian,
+            geode: geodes,
+        } = self.resources;
+        let mut resources = vec![];
+        if ore > 0 {
+            resources.push(format!("ore={ore}"));
+        }
+        if clay > 0 {
+            resources.push(format!("clay={clay}"));
+        }
+        if obsidian > 0 {
+            resources.push(format!("obsidian={obsidian}"));
+        }
+        if geodes > 0 {
+            resources.push(format!("geodes={geodes}"));
+        }
+
+        let Robots {
+            ore,
+            clay,
+            obsidian,
+            geode,
+        } = self.robots;
+        let mut robots = vec![];
+        if ore > 0 {
+            robots.push(format!("ore={ore}"));
+        }
+        if clay > 0 {
+            robots.push(format!("clay={clay}"));
+        }
+        if obsidian > 0 {
+            robots.push(format!("obsidian={obsidian}"));
+        }
+        if geode > 0 {
+            robots.push(format!("geode={geode}"));
+        }
+
+        let robots = robots.join(", ");
+        let resources = resources.join(", ");
+        write!(f, "t={t}, resources: {{{resources}}}, robots: {{{robots}}}")
+    }
+}
+
+const DEBUG: bool = false;
+
+struct Search<'a> {
+    blueprint: &'a Blueprint,
+    most_spent: Resources,
+    time_limit: u8,
+    max_geodes: ResourceCount,
+    /// The robots built so far, and the minutes building them started.
+    builds: Vec<(u8, Robot)>,
+    best_builds: Vec<(u8, Robot)>,
+    states: u64,
+}
+
+impl Search<'_> {
+    /// Try building every kind of robot next from `state`, skipping ahead to
+    /// when it can be built, unless even the most optimistic outcome from
+    /// there can't beat the best so far.
+    fn search(&mut self, state: &State) {
+        if DEBUG {
+            if self.states.is_multiple_of(1_000_000) {
+                println!(
+                    "After {} states: max_geodes={}",
+                    self.states, self.max_geodes
+                );
+            }
+            println!("Simulating {state}");
+        }
+        self.states += 1;
+
+        // If no more robot purchases, process the number of geodes mined.
+        let geodes_with_no_further_buys = state.advance_to_end(self.time_limit);
+        if geodes_with_no_further_buys > self.max_geodes {
+            self.max_geodes = geodes_with_no_further_buys;
+            self.best_builds = self.builds.clone();
+            if DEBUG {
+                println!("new max_geodes={}", self.max_geodes);
+            }
+        }
+
+        // Prune away any state that can never exceed current max geodes.
+        let upper_bound = state.max_geodes_achievable(self.blueprint, self.time_limit);
+        if upper_bound <= self.max_geodes {
+            return;
+        }
+
+        for robot in ROBOTS {
+            if !state.worth_buying(robot, &self.most_spent, self.time_limit) {
+                continue;
+            }
+            if let Some(new_sim) = state.buy_robot(robot, self.blueprint, self.time_limit) {
+                if DEBUG {
+                    println!("  adding {new_sim}");
+                }
+                self.builds.push((new_sim.time, robot));
+                self.search(&new_sim);
+                self.builds.pop();
+            }
+        }
+    }
+}
+
+/// What happens in one minute of a schedule.
+#[derive(Clone, Debug)]
+struct Minute {
+    /// The robot whose building started this minute, and what it cost.
+    built: Option<(Robot, Resources)>,
+    /// How things stand at the end of the minute.
+    state: State,
+}
+
+/// The robots to build with a blueprint to open the most geodes, and what
+/// happens each minute building them.
+#[derive(Clone, Debug)]
+pub struct Schedule {
+    blueprint_id: u16,
+    minutes: Vec<Minute>,
+}
+
+impl Schedule {
+    /// Build each robot in `builds` in the minute given with it, for
+    /// `time_limit` minutes.
+    fn replay(blueprint: &Blueprint, builds: &[(u8, Robot)], time_limit: u8) -> Schedule {
+        let mut state = State::START;
+        let minutes = (1..=time_limit)
+            .map(|minute| {
+                let built = builds
+                    .iter()
+                    .find(|&&(start, _)| start == minute)
+                    .map(|&(_, robot)| (robot, *blueprint.cost(robot)));
+                match built {
+                    Some((robot, _)) => state.build(robot, blueprint),
+                    None => state.minutes(1),
+                }
+                Minute { built, state }
+            })
+            .collect();
+
+        Schedule {
+            blueprint_id: blueprint.id,
+            minutes,
+        }
+    }
+
+    pub fn blueprint_id(&self) -> u16 {
+        self.blueprint_id
+    }
+
+    /// The geodes open at the end.
+    pub fn geodes(&self) -> u16 {
+        self.minutes
+            .last()
+            .map_or(0, |minute| minute.state.resources.geode)
+    }
+}
+
+/// Minute by minute, as the puzzle tells it, or with the alternate flag
+/// (`{:#}`), a line of resources and robots after each minute.
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            writeln!(f, "{}", State::START)?;
+            for Minute { built, state } in &self.minutes {
+                write!(f, "{state}")?;
+                if let Some((robot, _)) = built {
+                    write!(f, ", built {} {} robot", robot.article(), robot.kind())?;
+                }
+                writeln!(f)?;
+            }
+            return Ok(());
+        }
+
+        for (i, Minute { built, state }) in self.minutes.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "== Minute {} ==", state.time)?;
+
+            if let Some((robot, cost)) = built {
+                let cost = [
+                    (cost.ore, "ore"),
+                    (cost.clay, "clay"),
+                    (cost.obsidian, "obsidian"),
+                ]
+                .into_iter()
+                .filter(|&(amount, _)| amount > 0)
+                .map(|(amount, resource)| format!("{amount} {resource}"))
+                .collect::<Vec<_>>()
+                .join(" and ");
+                writeln!(
+                    f,
+                    "Spend {cost} to start building {} {} robot.",
+                    robot.article(),
+                    robot.kind()
+                )?;
+            }
+
+            for robot in [Robot::Ore, Robot::Clay, Robot::Obsidian, Robot::Geode] {
+                // The robot being built doesn't collect anything yet.
+                let count = state.robots.count(robot)
+                    - RobotCount::from(built.is_some_and(|(new, _)| new == robot));
+                if count == 0 {
+                    continue;
+                }
+
+                let total = state.resources.collected_by(robot);
+                let (robots, verb) = match (robot, count) {
+                    (Robot::Geode, 1) => ("robot", "cracks"),
+                    (Robot::Geode, _) => ("robots", "crack"),
+                    (_, 1) => ("robot", "collects"),
+                    (_, _) => ("robots", "collect"),
+                };
+                let (collected, now) = match robot {
+                    Robot::Ore => ("ore".to_string(), "ore".to_string()),
+                    Robot::Clay => ("clay".to_string(), "clay".to_string()),
+                    Robot::Obsidian => ("obsidian".to_string(), "obsidian".to_string()),
+                    Robot::Geode => {
+                        let geodes = |n| if n == 1 { "geode" } else { "geodes" };
+                        (geodes(count).to_string(), format!("open {}", geodes(total)))
+                    }
+                };
+                writeln!(
+                    f,
+                    "{count} {} {robots} {verb} {count} {collected}; you now have {total} {now}.",
+                    robot.kind()
+                )?;
+            }
+
+            if let Some((robot, _)) = built {
+                writeln!(
+                    f,
+                    "The new {} robot is ready; you now have {} of them.",
+                    robot.kind(),
+                    state.robots.count(*robot)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The schedule opening the most geodes with `blueprint` in `time_limit`
+/// minutes.
+fn best_schedule(blueprint: &Blueprint, time_limit: u8) -> Schedule {
+    let mut search = Search {
+        blueprint,
+        most_spent: blueprint.most_spent(),
+        time_limit,
+        max_geodes: 0,
+        builds: vec![],
+        best_builds: vec![],
+        states: 0,
+    };
+    search.search(&State::START);
+
+    if DEBUG {
+        println!(
+            "max geodes: {} after {} states",
+            search.max_geodes, search.states
+        );
+    }
+    Schedule::replay(blueprint, &search.best_builds, time_limit)
+}
+
+#[cfg(test)]
+fn compute_max_geodes(blueprint: &Blueprint, time_limit: u8) -> u16 {
+    best_schedule(blueprint, time_limit).geodes()
+}
+
+/// The best schedule for each blueprint for `time_limit` minutes, working
+/// out several blueprints at once on separate threads.
+pub fn best_schedules(blueprints: &[Blueprint], time_limit: u8) -> Vec<Schedule> {
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(blueprints.len());
+
+    // Each thread takes the next blueprint no thread has taken yet, so that a
+    // thread that's slow on one blueprint doesn't hold up the others.
+    let next = AtomicUsize::new(0);
+    let mut schedules = vec![None; blueprints.len()];
+    thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut found = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(blueprint) = blueprints.get(i) else {
+                            return found;
+                        };
+                        found.push((i, best_schedule(blueprint, time_limit)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            for (i, schedule) in worker.join().expect("no panics finding schedules") {
+                schedules[i] = Some(schedule);
+            }
+        }
+    });
+    schedules
+        .into_iter()
+        .map(|schedule| schedule.expect("a schedule for every blueprint"))
+        .collect()
+}
+
+/// The most geodes each blueprint can open in `time_limit` minutes.
+fn compute_max_geodes_each(blueprints: &[Blueprint], time_limit: u8) -> Vec<u16> {
+    best_schedules(blueprints, time_limit)
+        .iter()
+        .map(Schedule::geodes)
+        .collect()
+}
+
+pub const PART1_TIME: u8 = 24;
+
+#[cfg(test)]
+fn quality_level(blueprint: &Blueprint, time_limit: u8) -> u16 {
+    let id = blueprint.id;
+    let max_geodes = compute_max_geodes(blueprint, time_limit);
+    id * max_geodes
+}
+
+fn sum_quality_levels(blueprints: &[Blueprint], time: u8) -> u64 {
+    blueprints
+        .iter()
+        .zip(compute_max_geodes_each(blueprints, time))
+        .map(|(bp, max_geodes)| u64::from(bp.id) * u64::from(max_geodes))
+        .sum()
+}
+
+pub const PART2_TIME: u8 = 32;
+
+fn first_three_max_geodes_product(blueprints: &[Blueprint], time: u8) -> u64 {
+    let first_three = &blueprints[..blueprints.len().min(3)];
+    compute_max_geodes_each(first_three, time)
+        .into_iter()
+        .map(u64::from)
+        .product()
+}
+
+#[test]
+fn test_example() {
+    static INPUT: &str = "Blueprint 1:
+    Each ore robot costs 4 ore.
+    Each clay robot costs 2 ore.
+    Each obsidian robot costs 3 ore and 14 clay.
+    Each geode robot costs 2 ore and 7 obsidian.
+
+  Blueprint 2:
+    Each ore robot costs 2 ore.
+    Each clay robot costs 3 ore.
+    Each obsidian robot costs 3 ore and 8 clay.
+    Each geode robot costs 3 ore and 12 obsidian.";
+
+    let blueprints = parse_input(INPUT, "\n\n").unwrap();
+    assert_eq!(
+        blueprints,
+        vec![
+            Blueprint {
+                id: 1,
+                ore_robot_cost: Resources {
+                    ore: 4,
+                    clay: 0,
+                    obsidian: 0,
+                    geode: 0
+                },
+                clay_robot_cost: Resources {
+                    ore: 2,
+                    clay: 0,
+                    obsidian: 0,
+                    geode: 0
+                },
+                obsidian_robot_cost: Resources {
+                    ore: 3,
+                    clay: 14,
+                    obsidian: 0,
+                    geode: 0
+                },
+                geode_robot_cost: Resources {
+                    ore: 2,
+                    obsidian: 7,
+                    clay: 0,
+                    geode: 0,
+                },
+            },
+            Blueprint {
+                id: 2,
+                ore_robot_cost: Resources {
+                    ore: 2,
+                    clay: 0,
+                    obsidian: 0,
+                    geode: 0,
+                },
+                clay_robot_cost: Resources {
+                    ore: 3,
+                    clay: 0,
+                    obsidian: 0,
+                    geode: 0
+                },
+                obsidian_robot_cost: Resources {
+                    ore: 3,
+                    clay: 8,
+                    obsidian: 0,
+                    geode: 0
+                },
+                geode_robot_cost: Resources {
+                    ore: 3,
+                    obsidian: 12,
+                    clay: 0,
+                    geode: 0
+                },
+            }
+        ]
+    );
+
+    // Part 1.
+    let bp1_ql = quality_level(&blueprints[0], PART1_TIME);
+    println!("Blueprint 1 quality level (in t={PART1_TIME}): {bp1_ql}");
+    assert_eq!(bp1_ql, 9);
+
+    let bp2_ql = quality_level(&blueprints[1], PART1_TIME);
+    println!("Blueprint 2 quality level (in t={PART1_TIME}): {bp2_ql}");
+    assert_eq!(bp2_ql, 24);
+
+    // Part 2.
+    let bp1_geodes = compute_max_geodes(&blueprints[0], PART2_TIME);
+    println!("Blueprint 1 max geodes (in t={PART2_TIME}): {bp1_geodes}");
+    assert_eq!(bp1_geodes, 56);
+
+    let bp2_geodes = compute_max_geodes(&blueprints[1], PART2_TIME);
+    println!("Blueprint 2 max geodes (in t={PART2_TIME}): {bp2_geodes}");
+    assert_eq!(bp2_geodes, 62);
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 19;
+
+    type Parsed<'a> = Vec<Blueprint>;
+
+    fn parse(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+        parse_input(input, "\n")
+    }
+
+    fn part1(blueprints: &Vec<Blueprint>) -> String {
+        sum_quality_levels(blueprints, PART1_TIME).to_string()
+    }
+
+    fn part2(blueprints: &Vec<Blueprint>) -> String {
+        first_three_max_geodes_product(blueprints, PART2_TIME).to_string()
+    }
+}
+
+#[test]
+fn bad_blueprint() {
+    let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+                 Each obsidian robot costs 3 ore and 14 clay. \
+                 Each geode robot costs 2 ore and 7 clay.";
+
+    let error = parse_input(input, "\n").err().unwrap().locate(input);
+    assert_eq!(error.expected, "\"obsidian.\"");
+    assert_eq!(error.text, "clay.");
+}
+
+#[test]
+fn longer_times() {
+    let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+                 Each obsidian robot costs 3 ore and 14 clay. \
+                 Each geode robot costs 2 ore and 7 obsidian.
+                 Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+                 Each obsidian robot costs 3 ore and 8 clay. \
+                 Each geode robot costs 3 ore and 12 obsidian.";
+    let blueprints = parse_input(input, "\n").unwrap();
+
+    // Working out blueprints in parallel finds the same as one at a time.
+    assert_eq!(
+        compute_max_geodes_each(&blueprints, PART2_TIME),
+        vec![56, 62]
+    );
+
+    // More time never means fewer geodes.
+    let mut previous = vec![0, 0];
+    for time in (PART1_TIME..=40).step_by(4) {
+        let max_geodes = compute_max_geodes_each(&blueprints, time);
+        assert!(max_geodes
+            .iter()
+            .zip(&previous)
+            .all(|(now, then)| now >= then));
+        previous = max_geodes;
+    }
+    assert_eq!(
+        first_three_max_geodes_product(&blueprints, 40),
+        u64::from(previous[0]) * u64::from(previous[1])
+    );
+
+    // Three blueprints' geodes multiply out past what a u16 holds.
+    let three = parse_input(
+        &format!(
+            "{input}
+             Blueprint 3: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+             Each obsidian robot costs 3 ore and 14 clay. \
+             Each geode robot costs 2 ore and 7 obsidian."
+        ),
+        "\n",
+    )
+    .unwrap();
+    let product = first_three_max_geodes_product(&three, 40);
+    assert_eq!(
+        product,
+        u64::from(previous[0]) * u64::from(previous[1]) * u64::from(previous[0])
+    );
+    assert!(product > u64::from(u16::MAX));
+}
+
+#[test]
+fn narrative() {
+    let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+                 Each obsidian robot costs 3 ore and 14 clay. \
+                 Each geode robot costs 2 ore and 7 obsidian.";
+    let blueprints = parse_input(input, "\n").unwrap();
+
+    // The puzzle's schedule.
+    let builds = [
+        (3, Robot::Clay),
+        (5, Robot::Clay),
+        (7, Robot::Clay),
+        (11, Robot::Obsidian),
+        (12, Robot::Clay),
+        (15, Robot::Obsidian),
+        (18, Robot::Geode),
+        (21, Robot::Geode),
+    ];
+    let schedule = Schedule::replay(&blueprints[0], &builds, PART1_TIME);
+    assert_eq!(schedule.geodes(), 9);
+    let narrative = schedule.to_string();
+    assert!(narrative.starts_with(
+        "== Minute 1 ==
+1 ore-collecting robot collects 1 ore; you now have 1 ore.
+
+== Minute 2 ==
+1 ore-collecting robot collects 1 ore; you now have 2 ore.
+
+== Minute 3 ==
+Spend 2 ore to start building a clay-collecting robot.
+1 ore-collecting robot collects 1 ore; you now have 1 ore.
+The new clay-collecting robot is ready; you now have 1 of them.
+"
+    ));
+    assert!(narrative.contains(
+        "== Minute 11 ==
+Spend 3 ore and 14 clay to start building an obsidian-collecting robot.
+1 ore-collecting robot collects 1 ore; you now have 2 ore.
+3 clay-collecting robots collect 3 clay; you now have 4 clay.
+The new obsidian-collecting robot is ready; you now have 1 of them.
+"
+    ));
+    assert!(narrative.ends_with(
+        "== Minute 24 ==
+1 ore-collecting robot collects 1 ore; you now have 6 ore.
+4 clay-collecting robots collect 4 clay; you now have 41 clay.
+2 obsidian-collecting robots collect 2 obsidian; you now have 8 obsidian.
+2 geode-cracking robots crack 2 geodes; you now have 9 open geodes.
+"
+    ));
+    assert_eq!(
+        format!("{schedule:#}").lines().nth(18),
+        Some(
+            "t=18, resources: {ore=2, clay=17, obsidian=3}, \
+             robots: {ore=1, clay=4, obsidian=2, geode=1}, built a geode-cracking robot"
+        )
+    );
+
+    // The best schedule found opens as many geodes, and builds them as told.
+    let best = best_schedule(&blueprints[0], PART1_TIME);
+    assert_eq!(best.geodes(), 9);
+    assert!(best.to_string().ends_with("you now have 9 open geodes.\n"));
+}
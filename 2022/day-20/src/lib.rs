@@ -0,0 +1,635 @@
+#![forbid(unsafe_code)]
+
+use aoc_common::{parse, ParseError, Solution};
+use std::fmt;
+
+/// No node: an absent child or parent.
+const NIL: usize = usize::MAX;
+
+/// A number in the list, and its place in the tree keeping the list's order.
+struct Node {
+    value: i64,
+    /// The tree is a heap on these random priorities, which keeps it
+    /// balanced (most likely).
+    priority: u64,
+    left: usize,
+    right: usize,
+    parent: usize,
+    /// The number of nodes in the subtree rooted here.
+    size: usize,
+}
+
+#[cfg(test)]
+const DEBUG: bool = true;
+
+#[cfg(test)]
+fn if_debug<F>(f: F)
+where
+    F: Fn(),
+{
+    if DEBUG {
+        f();
+    }
+}
+
+/// A circular list of numbers, which can move any number a given distance
+/// around the circle in O(log n) time.
+///
+/// The numbers are kept in an arena, in the order they were added, so that
+/// they can be mixed in that order.  They're also the nodes of a tree (a
+/// treap) whose in-order traversal is their order in the list, starting
+/// anywhere in the circle.  Each node knows the size of its subtree, so a
+/// node's position in the list can be found, and the list split and joined
+/// at any position, in time proportional to the tree's height.
+struct CircularLinkedList {
+    nodes: Vec<Node>,
+    root: usize,
+    zero: Option<usize>,
+    /// State for generating priorities.
+    seed: u64,
+}
+
+impl CircularLinkedList {
+    fn new(first_value: i64) -> CircularLinkedList {
+        let mut list = CircularLinkedList {
+            nodes: vec![],
+            root: NIL,
+            zero: None,
+            seed: 0x2022_0020,
+        };
+        list.append(first_value);
+        list
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].size
+        }
+    }
+
+    fn set_parent(&mut self, node: usize, parent: usize) {
+        if node != NIL {
+            self.nodes[node].parent = parent;
+        }
+    }
+
+    /// Fix up `node`'s size and its children's parent after its children
+    /// change.
+    fn update(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = 1 + self.size(left) + self.size(right);
+        self.set_parent(left, node);
+        self.set_parent(right, node);
+    }
+
+    /// Join the trees `a` and `b`, with all of `a` before all of `b`.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+
+        if self.nodes[a].priority > self.nodes[b].priority {
+            let right = self.nodes[a].right;
+            self.nodes[a].right = self.merge(right, b);
+            self.update(a);
+            a
+        } else {
+            let left = self.nodes[b].left;
+            self.nodes[b].left = self.merge(a, left);
+            self.update(b);
+            b
+        }
+    }
+
+    /// Split the tree `node` into its first `count` nodes and the rest.
+    fn split(&mut self, node: usize, count: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+
+        let left = self.nodes[node].left;
+        let left_size = self.size(left);
+        if count <= left_size {
+            let (first, rest) = self.split(left, count);
+            self.nodes[node].left = rest;
+            self.update(node);
+            self.set_parent(first, NIL);
+            (first, node)
+        } else {
+            let right = self.nodes[node].right;
+            let (first, rest) = self.split(right, count - left_size - 1);
+            self.nodes[node].right = first;
+            self.update(node);
+            self.set_parent(rest, NIL);
+            (node, rest)
+        }
+    }
+
+    fn set_root(&mut self, root: usize) {
+        self.root = root;
+        self.set_parent(root, NIL);
+    }
+
+    /// Where `node` is in the list.
+    fn position(&self, node: usize) -> usize {
+        let mut position = self.size(self.nodes[node].left);
+        let mut node = node;
+        loop {
+            let parent = self.nodes[node].parent;
+            if parent == NIL {
+                return position;
+            }
+            if self.nodes[parent].right == node {
+                position += self.size(self.nodes[parent].left) + 1;
+            }
+            node = parent;
+        }
+    }
+
+    /// The node at `position` in the list.
+    fn at(&self, mut position: usize) -> usize {
+        let mut node = self.root;
+        loop {
+            let left = self.nodes[node].left;
+            let left_size = self.size(left);
+            if position < left_size {
+                node = left;
+            } else if position == left_size {
+                return node;
+            } else {
+                position -= left_size + 1;
+                node = self.nodes[node].right;
+            }
+        }
+    }
+
+    fn append(&mut self, value: i64) {
+        // A 64-bit xorshift.
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            value,
+            priority: self.seed,
+            left: NIL,
+            right: NIL,
+            parent: NIL,
+            size: 1,
+        });
+
+        if value == 0 {
+            assert!(self.zero.is_none(), "should only have one zero");
+            self.zero = Some(node);
+        }
+
+        let root = self.merge(self.root, node);
+        self.set_root(root);
+    }
+
+    #[cfg(test)]
+    fn zero_iter(&self) -> Option<ZeroIter<'_>> {
+        self.zero.map(|zero| ZeroIter {
+            list: self,
+            position: self.position(zero),
+        })
+    }
+
+    /// Move the `node`th number added as many places around the circle as
+    /// its value, forward if positive and backward if negative.
+    fn mix_node(&mut self, node: usize) {
+        let others = self.len() - 1;
+        if others == 0 {
+            return;
+        }
+
+        // Take the number out, then put it back in its new position among
+        // the others.  As the list is circular, it doesn't matter where
+        // within the tree the circle starts.
+        let position = self.position(node);
+        let (before, rest) = self.split(self.root, position);
+        let (node, after) = self.split(rest, 1);
+        let others_root = self.merge(before, after);
+
+        // Going all the way around the others leaves the number where it
+        // was, so only the remainder of the move matters.
+        let shift = self.nodes[node].value.rem_euclid(others as i64) as usize;
+        let new_position = (position + shift) % others;
+        let (before, after) = self.split(others_root, new_position);
+        let before = self.merge(before, node);
+        let root = self.merge(before, after);
+        self.set_root(root);
+    }
+
+    fn mix(&mut self) {
+        for node in 0..self.len() {
+            self.mix_node(node);
+        }
+    }
+
+    fn mix_rounds(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.mix();
+        }
+    }
+}
+
+impl fmt::Display for CircularLinkedList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for position in 0..self.len() {
+            if position > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", self.nodes[self.at(position)].value)?;
+        }
+        Ok(())
+    }
+}
+
+/// The numbers in the list going around and around the circle, starting
+/// with zero.
+#[cfg(test)]
+struct ZeroIter<'a> {
+    list: &'a CircularLinkedList,
+    position: usize,
+}
+
+#[cfg(test)]
+impl Iterator for ZeroIter<'_> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let value = self.list.nodes[self.list.at(self.position)].value;
+        self.position = (self.position + 1) % self.list.len();
+        Some(value)
+    }
+}
+
+/// The encrypted file's numbers, of which there must be at least one, and
+/// exactly one of which must be zero.
+fn parse_encrypted(s: &str) -> Result<Vec<i64>, ParseError> {
+    let mut numbers = vec![];
+    let mut seen_zero = false;
+    for line in s.trim().split('\n') {
+        let number = parse::value(line, "a number")?;
+        if number == 0 {
+            if seen_zero {
+                return Err(ParseError::new(line, "only one zero"));
+            }
+            seen_zero = true;
+        }
+        numbers.push(number);
+    }
+
+    if !seen_zero {
+        return Err(ParseError::after(s, "a zero"));
+    }
+
+    Ok(numbers)
+}
+
+fn to_circular_linked_list(encrypted: &[i64], key: i64) -> CircularLinkedList {
+    let (&n, rest) = encrypted.split_first().expect("nonempty encrypted list");
+
+    let mut cll = CircularLinkedList::new(n * key);
+    for n in rest {
+        cll.append(n * key);
+    }
+
+    cll
+}
+
+/// The numbers `offsets` places after zero, going around the circle.
+fn coordinates(cll: &CircularLinkedList, offsets: &[usize]) -> Vec<i64> {
+    let zero = cll.position(cll.zero.expect("zero in cll"));
+    offsets
+        .iter()
+        .map(|offset| cll.nodes[cll.at((zero + offset) % cll.len())].value)
+        .collect()
+}
+
+#[cfg(test)]
+fn expect_thousands(cll: &CircularLinkedList, expected_thousands: [i64; 3]) {
+    if_debug(|| {
+        if cll.len() < 10 {
+            println!("cll: {}", cll);
+        }
+    });
+
+    let thousands = coordinates(cll, &COORDINATE_OFFSETS);
+
+    println!(
+        "Coordinates: {}",
+        thousands
+            .iter()
+            .map(<_>::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    assert_eq!(thousands, expected_thousands);
+
+    let coord_sum: i64 = thousands.iter().sum();
+    let expected_sum: i64 = expected_thousands.iter().copied().sum();
+    println!("Observed coordinate sum: {coord_sum}");
+    println!("Expected coordinate sum: {expected_sum}");
+    assert_eq!(coord_sum, expected_sum);
+}
+
+pub const PART1_KEY: i64 = 1;
+pub const PART1_ROUNDS: usize = 1;
+pub const PART2_KEY: i64 = 811_589_153;
+pub const PART2_ROUNDS: usize = 10;
+
+/// How far after zero the grove coordinates are.
+pub const COORDINATE_OFFSETS: [usize; 3] = [1000, 2000, 3000];
+
+/// Decrypt the `encrypted` file by multiplying each number by `key`, then
+/// mixing `rounds` times, and return the numbers `offsets` places after zero.
+/// The file must hold exactly one zero, and the key mustn't be zero or make
+/// any number too big for an `i64`.
+pub fn decrypt(
+    encrypted: &[i64],
+    key: i64,
+    rounds: usize,
+    offsets: &[usize],
+) -> Result<Vec<i64>, String> {
+    if key == 0 {
+        return Err("a key of zero would make every number zero".to_string());
+    }
+    match encrypted.iter().filter(|&&n| n == 0).count() {
+        1 => {}
+        zeros => return Err(format!("the file holds {zeros} zeros, not one")),
+    }
+    if let Some(n) = encrypted.iter().find(|n| n.checked_mul(key).is_none()) {
+        return Err(format!("{n} times the key {key} is too big"));
+    }
+
+    let mut cll = to_circular_linked_list(encrypted, key);
+    cll.mix_rounds(rounds);
+    Ok(coordinates(&cll, offsets))
+}
+
+fn grove_coordinates_sum(encrypted: &[i64], key: i64, rounds: usize) -> String {
+    match decrypt(encrypted, key, rounds, &COORDINATE_OFFSETS) {
+        Ok(coordinates) => coordinates
+            .iter()
+            .map(|&n| i128::from(n))
+            .sum::<i128>()
+            .to_string(),
+        Err(error) => error,
+    }
+}
+
+#[test]
+fn given_example() {
+    static ENCRYPTED: &str = "1
+2
+-3
+3
+-2
+0
+4";
+
+    let number_count = ENCRYPTED.lines().count();
+
+    let mut cll = to_circular_linked_list(&parse_encrypted(ENCRYPTED).unwrap(), PART1_KEY);
+    cll.mix();
+
+    assert_eq!(
+        cll.zero_iter()
+            .expect("contains zero")
+            .take(number_count)
+            .collect::<Vec<_>>(),
+        [0, 3, -2, 1, 2, -3, 4]
+    );
+
+    expect_thousands(&cll, [4, -3, 2]);
+
+    let mut cll = to_circular_linked_list(&parse_encrypted(ENCRYPTED).unwrap(), PART2_KEY);
+
+    assert_eq!(
+        cll.zero_iter()
+            .expect("contains zero")
+            .take(number_count)
+            .collect::<Vec<_>>(),
+        [
+            0,
+            3246356612,
+            811589153,
+            1623178306,
+            -2434767459,
+            2434767459,
+            -1623178306,
+        ]
+    );
+
+    cll.mix_rounds(PART2_ROUNDS);
+
+    assert_eq!(
+        cll.zero_iter()
+            .expect("contains zero")
+            .take(number_count)
+            .collect::<Vec<_>>(),
+        [
+            0,
+            -2434767459,
+            1623178306,
+            3246356612,
+            -1623178306,
+            2434767459,
+            811589153
+        ]
+    );
+}
+
+#[test]
+fn adjusted_example_positive() {
+    static ENCRYPTED: &str = "0
+1
+2
+3";
+
+    let mut cll = to_circular_linked_list(&parse_encrypted(ENCRYPTED).unwrap(), PART1_KEY);
+    cll.mix();
+
+    assert_eq!(
+        cll.zero_iter().unwrap().take(cll.len()).collect::<Vec<_>>(),
+        vec![0, 1, 3, 2]
+    );
+
+    expect_thousands(&cll, [0, 0, 0]);
+}
+
+#[test]
+fn adjusted_example_negative() {
+    static ENCRYPTED: &str = "0
+1
+2
+-3";
+
+    let mut cll = to_circular_linked_list(&parse_encrypted(ENCRYPTED).unwrap(), PART1_KEY);
+    cll.mix();
+
+    assert_eq!(
+        cll.zero_iter().unwrap().take(cll.len()).collect::<Vec<_>>(),
+        vec![0, 1, -3, 2]
+    );
+
+    expect_thousands(&cll, [0, 0, 0]);
+}
+
+#[test]
+fn two_elems() {
+    static ENCRYPTED: &str = "5
+0";
+
+    let mut cll = to_circular_linked_list(&parse_encrypted(ENCRYPTED).unwrap(), PART1_KEY);
+    cll.mix();
+
+    expect_thousands(&cll, [0, 0, 0]);
+}
+
+#[test]
+fn exactly_one_zero() {
+    let input = "0\n5\n0";
+    let error = parse_encrypted(input).unwrap_err().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 1: expected only one zero, found \"0\""
+    );
+    assert!(parse_encrypted("5\n7").is_err());
+}
+
+#[test]
+fn decryption_variants() {
+    let encrypted = parse_encrypted("1\n2\n-3\n3\n-2\n0\n4").unwrap();
+
+    // Unmixed, the numbers after zero are as in the file.
+    assert_eq!(
+        decrypt(&encrypted, 1, 0, &[0, 1, 2, 7]).unwrap(),
+        [0, 4, 1, 0]
+    );
+    assert_eq!(decrypt(&encrypted, 2, 0, &[1, 3]).unwrap(), [8, 4]);
+
+    // The puzzle's rounds, looking at the whole circle.
+    assert_eq!(
+        decrypt(&encrypted, PART1_KEY, PART1_ROUNDS, &[0, 1, 2, 3, 4, 5, 6]).unwrap(),
+        [0, 3, -2, 1, 2, -3, 4]
+    );
+    assert_eq!(
+        decrypt(&encrypted, PART2_KEY, 1, &[0, 1, 2, 3, 4, 5, 6]).unwrap(),
+        [
+            0,
+            -2434767459,
+            3246356612,
+            -1623178306,
+            2434767459,
+            1623178306,
+            811589153
+        ]
+    );
+    assert_eq!(
+        decrypt(&encrypted, PART2_KEY, PART2_ROUNDS, &COORDINATE_OFFSETS).unwrap(),
+        [811589153, 2434767459, -1623178306]
+    );
+
+    // Keys and files that can't be decrypted.
+    assert!(decrypt(&encrypted, 0, 1, &[1]).is_err());
+    assert!(decrypt(&[], 1, 1, &[1]).is_err());
+    assert!(decrypt(&[0, 0, 1], 1, 1, &[1]).is_err());
+    assert!(decrypt(&encrypted, i64::MAX, 1, &[1]).is_err());
+
+    // A key big enough that moving a number by its whole value would
+    // overflow.  It's one more than a multiple of the six other numbers, so
+    // the numbers move as they would with a key of one.
+    let key = i64::MAX / 4;
+    assert_eq!(key % 6, 1);
+    let expected = decrypt(&encrypted, 1, 1, &[1, 2, 3, 4, 5, 6])
+        .unwrap()
+        .into_iter()
+        .map(|n| n * key)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        decrypt(&encrypted, key, 1, &[1, 2, 3, 4, 5, 6]).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn matches_moving_within_a_vec() {
+    // Mix by removing and reinserting each number in a plain vector, which
+    // takes linear time per move but is easy to get right.
+    fn mix_slowly(numbers: &mut Vec<(usize, i64)>) {
+        for i in 0..numbers.len() {
+            let from = numbers.iter().position(|&(j, _)| j == i).unwrap();
+            let number = numbers.remove(from);
+            let to = (from as i64 + number.1).rem_euclid(numbers.len() as i64);
+            numbers.insert(to as usize, number);
+        }
+    }
+
+    let mut seed = 1u64;
+    let encrypted = (0..200)
+        .map(|i| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            if i == 77 {
+                0
+            } else {
+                (seed >> 40) as i64 % 1000 - 500
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for key in [PART1_KEY, PART2_KEY] {
+        let mut cll = to_circular_linked_list(&encrypted, key);
+        let mut numbers = encrypted.iter().map(|n| n * key).enumerate().collect();
+        for _ in 0..3 {
+            cll.mix();
+            mix_slowly(&mut numbers);
+
+            let zero = numbers.iter().position(|&(_, n)| n == 0).unwrap();
+            let expected = numbers[zero..]
+                .iter()
+                .chain(&numbers[..zero])
+                .map(|&(_, n)| n)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                cll.zero_iter().unwrap().take(cll.len()).collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 20;
+
+    type Parsed<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse_encrypted(input)
+    }
+
+    fn part1(encrypted: &Vec<i64>) -> String {
+        grove_coordinates_sum(encrypted, PART1_KEY, PART1_ROUNDS)
+    }
+
+    fn part2(encrypted: &Vec<i64>) -> String {
+        grove_coordinates_sum(encrypted, PART2_KEY, PART2_ROUNDS)
+    }
+}
+
+aoc_common::example_tests!(Day20);
@@ -0,0 +1,708 @@
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{self, Write};
+
+type Monkey = String;
+type MonkeyNumber = i64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum YellType {
+    Add,
+    Sub,
+    Div,
+    Mul,
+}
+
+impl YellType {
+    fn symbol(self) -> char {
+        match self {
+            YellType::Add => '+',
+            YellType::Sub => '-',
+            YellType::Div => '/',
+            YellType::Mul => '*',
+        }
+    }
+}
+
+#[derive(Clone)]
+struct YellEquation {
+    yell_type: YellType,
+    args: (Monkey, Monkey),
+}
+
+#[derive(Clone)]
+enum Yell {
+    Number(MonkeyNumber),
+    Equation(YellEquation),
+}
+
+#[derive(Clone)]
+pub struct MonkeyYell {
+    name: Monkey,
+    yell: Yell,
+}
+
+fn parse_monkey_yell(s: &str) -> Result<MonkeyYell, ParseError> {
+    // nsdv: czts * nlpw
+    // mrgj: 3
+    let (monkey_name, rest) = parse::split_once(s, ": ")?;
+
+    Ok(MonkeyYell {
+        name: monkey_name.to_string(),
+        yell: if let Ok(n) = rest.parse() {
+            Yell::Number(n)
+        } else {
+            let mut words = rest.split(' ');
+            let x = parse::next(&mut words, rest, "a monkey")?;
+            let op = parse::next(&mut words, rest, "an operation")?;
+            let y = parse::next(&mut words, rest, "a monkey")?;
+            if let Some(extra) = words.next() {
+                return Err(ParseError::new(extra, "the end of the equation"));
+            }
+
+            Yell::Equation(YellEquation {
+                yell_type: match op {
+                    "+" => YellType::Add,
+                    "-" => YellType::Sub,
+                    "/" => YellType::Div,
+                    "*" => YellType::Mul,
+                    other => return Err(ParseError::new(other, "one of + - / *")),
+                },
+                args: (x.to_string(), y.to_string()),
+            })
+        },
+    })
+}
+
+fn parse_monkey_yells(input: &str) -> Result<Vec<MonkeyYell>, ParseError> {
+    let yells = input
+        .trim()
+        .lines()
+        .map(parse_monkey_yell)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match yells.iter().find(|MonkeyYell { name, .. }| name == "root") {
+        Some(MonkeyYell {
+            yell: Yell::Equation(_),
+            ..
+        }) => Ok(yells),
+        _ => Err(ParseError::after(
+            input,
+            "a root monkey yelling an equation",
+        )),
+    }
+}
+
+/// The name of the monkey you stand in for in part 2.
+pub const YOU_NAME: &str = "humn";
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// An exact fraction, always in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    fn new(numer: i128, denom: i128) -> Option<Rational> {
+        if denom == 0 {
+            return None;
+        }
+
+        let divisor = i128::try_from(gcd(numer.unsigned_abs(), denom.unsigned_abs())).ok()?;
+        let sign = denom.signum();
+        Some(Rational {
+            numer: sign * (numer / divisor),
+            denom: sign * (denom / divisor),
+        })
+    }
+
+    fn integer(n: MonkeyNumber) -> Rational {
+        Rational {
+            numer: i128::from(n),
+            denom: 1,
+        }
+    }
+
+    fn to_integer(self) -> Option<MonkeyNumber> {
+        if self.denom != 1 {
+            return None;
+        }
+        MonkeyNumber::try_from(self.numer).ok()
+    }
+
+    fn add(self, other: Rational) -> Option<Rational> {
+        Rational::new(
+            self.numer
+                .checked_mul(other.denom)?
+                .checked_add(other.numer.checked_mul(self.denom)?)?,
+            self.denom.checked_mul(other.denom)?,
+        )
+    }
+
+    fn sub(self, other: Rational) -> Option<Rational> {
+        self.add(Rational {
+            numer: other.numer.checked_neg()?,
+            denom: other.denom,
+        })
+    }
+
+    fn mul(self, other: Rational) -> Option<Rational> {
+        Rational::new(
+            self.numer.checked_mul(other.numer)?,
+            self.denom.checked_mul(other.denom)?,
+        )
+    }
+
+    /// `self / other`, which must not be zero.
+    fn div(self, other: Rational) -> Option<Rational> {
+        Rational::new(
+            self.numer.checked_mul(other.denom)?,
+            self.denom.checked_mul(other.numer)?,
+        )
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// A monkey's number as `coefficient * x + constant`, where `x` is the number
+/// of the monkey being solved for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Linear {
+    coefficient: Rational,
+    constant: Rational,
+}
+
+impl Linear {
+    const UNKNOWN: Linear = Linear {
+        coefficient: Rational::ONE,
+        constant: Rational::ZERO,
+    };
+
+    fn constant(n: Rational) -> Linear {
+        Linear {
+            coefficient: Rational::ZERO,
+            constant: n,
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.coefficient == Rational::ZERO
+    }
+
+    /// This number written in terms of the `unknown` monkey's number.
+    fn describe(&self, unknown: &str) -> String {
+        if self.is_constant() {
+            return self.constant.to_string();
+        }
+
+        let mut s = match self.coefficient {
+            Rational::ONE => unknown.to_string(),
+            Rational {
+                numer: -1,
+                denom: 1,
+            } => format!("-{unknown}"),
+            coefficient => format!("{coefficient} {unknown}"),
+        };
+        if self.constant.numer > 0 {
+            write!(s, " + {}", self.constant).unwrap();
+        } else if self.constant.numer < 0 {
+            let magnitude = Rational {
+                numer: -self.constant.numer,
+                denom: self.constant.denom,
+            };
+            write!(s, " - {magnitude}").unwrap();
+        }
+        s
+    }
+
+    fn scale(self, factor: Rational) -> Option<Linear> {
+        Some(Linear {
+            coefficient: self.coefficient.mul(factor)?,
+            constant: self.constant.mul(factor)?,
+        })
+    }
+
+    /// Combine the numbers of the two monkeys `monkey` listens to.
+    fn combine(
+        self,
+        yell_type: YellType,
+        other: Linear,
+        monkey: &str,
+    ) -> Result<Linear, SolveError> {
+        let overflow = || SolveError::Overflow(monkey.to_string());
+        match yell_type {
+            YellType::Add => Ok(Linear {
+                coefficient: self
+                    .coefficient
+                    .add(other.coefficient)
+                    .ok_or_else(overflow)?,
+                constant: self.constant.add(other.constant).ok_or_else(overflow)?,
+            }),
+            YellType::Sub => Ok(Linear {
+                coefficient: self
+                    .coefficient
+                    .sub(other.coefficient)
+                    .ok_or_else(overflow)?,
+                constant: self.constant.sub(other.constant).ok_or_else(overflow)?,
+            }),
+            YellType::Mul => {
+                let (factor, scaled) = match (self.is_constant(), other.is_constant()) {
+                    (_, true) => (other.constant, self),
+                    (true, false) => (self.constant, other),
+                    (false, false) => return Err(SolveError::NonLinear(monkey.to_string())),
+                };
+                scaled.scale(factor).ok_or_else(overflow)
+            }
+            YellType::Div => {
+                if !other.is_constant() {
+                    return Err(SolveError::NonLinear(monkey.to_string()));
+                }
+                if other.constant == Rational::ZERO {
+                    return Err(SolveError::DivisionByZero(monkey.to_string()));
+                }
+                let reciprocal = Rational::ONE.div(other.constant).ok_or_else(overflow)?;
+                self.scale(reciprocal).ok_or_else(overflow)
+            }
+        }
+    }
+}
+
+/// Why the monkeys' numbers couldn't be worked out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    NoSuchMonkey(Monkey),
+    /// The monkey's number depends on itself.
+    Cycle(Monkey),
+    DivisionByZero(Monkey),
+    /// The monkey multiplies or divides by something depending on the
+    /// unknown, so the equation isn't linear.
+    NonLinear(Monkey),
+    Overflow(Monkey),
+    /// The monkey's number, or the only number it could yell to make the
+    /// equation hold, isn't an integer.
+    NotInteger(Monkey, Rational),
+    /// Root's numbers are equal whatever the unknown monkey yells.
+    AnyNumber,
+    /// Root's numbers are unequal whatever the unknown monkey yells.
+    NoNumber,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::NoSuchMonkey(monkey) => write!(f, "no monkey named {monkey}"),
+            SolveError::Cycle(monkey) => write!(f, "{monkey}'s number depends on itself"),
+            SolveError::DivisionByZero(monkey) => write!(f, "{monkey} divides by zero"),
+            SolveError::NonLinear(monkey) => {
+                write!(f, "{monkey}'s number isn't linear in the unknown")
+            }
+            SolveError::Overflow(monkey) => write!(f, "{monkey}'s number overflows"),
+            SolveError::NotInteger(monkey, n) => {
+                write!(f, "{monkey} would have to yell {n}, which isn't an integer")
+            }
+            SolveError::AnyNumber => write!(f, "root's numbers are always equal"),
+            SolveError::NoNumber => write!(f, "root's numbers are never equal"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// Works out monkeys' numbers, in terms of the number of the `unknown`
+/// monkey if there is one.
+struct Evaluator<'a> {
+    yells: HashMap<&'a str, &'a Yell>,
+    unknown: Option<&'a str>,
+    /// Numbers worked out so far, and why the ones that couldn't be worked
+    /// out couldn't, or `None` for numbers being worked out.
+    values: HashMap<&'a str, Option<Result<Linear, SolveError>>>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(yells: &'a [MonkeyYell], unknown: Option<&'a str>) -> Evaluator<'a> {
+        Evaluator {
+            yells: yells
+                .iter()
+                .map(|MonkeyYell { name, yell }| (name.as_str(), yell))
+                .collect(),
+            unknown,
+            values: HashMap::new(),
+        }
+    }
+
+    fn value(&mut self, monkey: &'a str) -> Result<Linear, SolveError> {
+        if Some(monkey) == self.unknown {
+            return Ok(Linear::UNKNOWN);
+        }
+
+        match self.values.get(monkey) {
+            Some(Some(value)) => return value.clone(),
+            Some(None) => return Err(SolveError::Cycle(monkey.to_string())),
+            None => {}
+        }
+
+        let yell = *self
+            .yells
+            .get(monkey)
+            .ok_or_else(|| SolveError::NoSuchMonkey(monkey.to_string()))?;
+
+        self.values.insert(monkey, None);
+        let value = self.work_out(monkey, yell);
+        self.values.insert(monkey, Some(value.clone()));
+
+        value
+    }
+
+    fn work_out(&mut self, monkey: &'a str, yell: &'a Yell) -> Result<Linear, SolveError> {
+        match yell {
+            Yell::Number(n) => Ok(Linear::constant(Rational::integer(*n))),
+            Yell::Equation(eqn) => {
+                let x = self.value(&eqn.args.0)?;
+                let y = self.value(&eqn.args.1)?;
+                x.combine(eqn.yell_type, y, monkey)
+            }
+        }
+    }
+
+    /// The two numbers the root monkey listens to.
+    fn root_args(&mut self) -> Result<(Linear, Linear), SolveError> {
+        let root = *self
+            .yells
+            .get("root")
+            .ok_or_else(|| SolveError::NoSuchMonkey("root".to_string()))?;
+        let Yell::Equation(eqn) = root else {
+            unreachable!("parsing checks that root yells an equation");
+        };
+
+        Ok((self.value(&eqn.args.0)?, self.value(&eqn.args.1)?))
+    }
+}
+
+fn compute_part1_root_monkey_number(yells: &[MonkeyYell]) -> Result<MonkeyNumber, SolveError> {
+    let root = Evaluator::new(yells, None).value("root")?.constant;
+    root.to_integer()
+        .ok_or_else(|| SolveError::NotInteger("root".to_string(), root))
+}
+
+/// The number `unknown` must yell for root's two numbers to be equal.  That
+/// number may feed into root's numbers along several paths, so long as they
+/// depend on it linearly.
+fn compute_part2_required_monkey_number(
+    yells: &[MonkeyYell],
+    unknown: &str,
+) -> Result<MonkeyNumber, SolveError> {
+    if !yells.iter().any(|MonkeyYell { name, .. }| name == unknown) {
+        return Err(SolveError::NoSuchMonkey(unknown.to_string()));
+    }
+
+    let (x, y) = Evaluator::new(yells, Some(unknown)).root_args()?;
+
+    // x == y exactly when (x - y) == a * unknown + b == 0.
+    let overflow = || SolveError::Overflow("root".to_string());
+    let a = x.coefficient.sub(y.coefficient).ok_or_else(overflow)?;
+    let b = x.constant.sub(y.constant).ok_or_else(overflow)?;
+    if a == Rational::ZERO {
+        return Err(if b == Rational::ZERO {
+            SolveError::AnyNumber
+        } else {
+            SolveError::NoNumber
+        });
+    }
+
+    let number = Rational::ZERO
+        .sub(b)
+        .and_then(|minus_b| minus_b.div(a))
+        .ok_or_else(overflow)?;
+    number
+        .to_integer()
+        .ok_or_else(|| SolveError::NotInteger(unknown.to_string(), number))
+}
+
+/// The monkeys as a Graphviz digraph, with an edge from each monkey to the
+/// monkeys it listens to.  Each monkey is labeled with what it yells and what
+/// that works out to in terms of the `unknown` monkey's number, and the
+/// monkeys through which that number reaches root are highlighted.
+pub fn dependency_dot(yells: &[MonkeyYell], unknown: &str) -> String {
+    let mut listened_to: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut listeners: HashMap<&str, Vec<&str>> = HashMap::new();
+    for MonkeyYell { name, yell } in yells {
+        if let Yell::Equation(eqn) = yell {
+            for arg in [&eqn.args.0, &eqn.args.1] {
+                listened_to.entry(name).or_default().push(arg);
+                listeners.entry(arg).or_default().push(name);
+            }
+        }
+    }
+
+    let reachable = |start: &str, edges: &HashMap<&str, Vec<&str>>| {
+        let mut seen = HashSet::from([start.to_string()]);
+        let mut pending = vec![start.to_string()];
+        while let Some(monkey) = pending.pop() {
+            for next in edges.get(monkey.as_str()).into_iter().flatten() {
+                if seen.insert(next.to_string()) {
+                    pending.push(next.to_string());
+                }
+            }
+        }
+        seen
+    };
+
+    // Monkeys root listens to that listen to the unknown monkey, directly or
+    // not.
+    let on_path = &reachable("root", &listened_to) & &reachable(unknown, &listeners);
+
+    let mut evaluator = Evaluator::new(yells, Some(unknown));
+
+    let mut dot = String::from("digraph monkeys {\n    node [shape=box];\n");
+    for MonkeyYell { name, yell } in yells {
+        let highlight = if on_path.contains(name) {
+            ", color=red, penwidth=2"
+        } else {
+            ""
+        };
+
+        let label = match yell {
+            _ if name == unknown => format!("{name}\\n?"),
+            Yell::Number(n) => format!("{name}\\n{n}"),
+            Yell::Equation(eqn) => {
+                let (x, y) = &eqn.args;
+                let mut label = format!("{name}\\n{x} {} {y}", eqn.yell_type.symbol());
+                match evaluator.value(name) {
+                    Ok(value) => write!(label, "\\n= {}", value.describe(unknown)).unwrap(),
+                    Err(error) => write!(label, "\\n{error}").unwrap(),
+                }
+                label
+            }
+        };
+        writeln!(dot, "    {name:?} [label=\"{label}\"{highlight}];").unwrap();
+
+        if let Yell::Equation(eqn) = yell {
+            for arg in [&eqn.args.0, &eqn.args.1] {
+                let highlight = if on_path.contains(name) && on_path.contains(arg) {
+                    " [color=red, penwidth=2]"
+                } else {
+                    ""
+                };
+                writeln!(dot, "    {name:?} -> {arg:?}{highlight};").unwrap();
+            }
+        }
+    }
+    dot.push_str("}\n");
+
+    dot
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    let yells = parse_monkey_yells(INPUT).unwrap();
+
+    let root_yells = compute_part1_root_monkey_number(&yells).unwrap();
+    println!("Part 1 root yells: {root_yells}");
+    assert_eq!(root_yells, 152);
+
+    let required_yells = compute_part2_required_monkey_number(&yells, YOU_NAME).unwrap();
+    println!("Part 2 required yells: {required_yells}");
+    assert_eq!(required_yells, 301);
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 21;
+
+    type Parsed<'a> = Vec<MonkeyYell>;
+
+    fn parse(input: &str) -> Result<Vec<MonkeyYell>, ParseError> {
+        parse_monkey_yells(input)
+    }
+
+    fn part1(yells: &Vec<MonkeyYell>) -> String {
+        match compute_part1_root_monkey_number(yells) {
+            Ok(number) => number.to_string(),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    fn part2(yells: &Vec<MonkeyYell>) -> String {
+        match compute_part2_required_monkey_number(yells, YOU_NAME) {
+            Ok(number) => number.to_string(),
+            Err(error) => error.to_string(),
+        }
+    }
+}
+
+aoc_common::example_tests!(Day21);
+
+#[test]
+fn bad_yell() {
+    let input = "root: pppw + sjmn\npppw: 5\nsjmn: pppw % 2";
+
+    let error = parse_monkey_yells(input).err().unwrap().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 12: expected one of + - / *, found \"%\""
+    );
+}
+
+#[test]
+fn unknown_in_several_places() {
+    // root: 3 * x + (x - 1) / 2 == 24, so x = 7.
+    let input = "root: sum + goal
+sum: trip + half
+trip: x * three
+half: less / two
+less: x - one
+goal: 24
+three: 3
+two: 2
+one: 1
+x: 7";
+    let yells = parse_monkey_yells(input).unwrap();
+    assert_eq!(compute_part2_required_monkey_number(&yells, "x"), Ok(7));
+
+    // Solving for some other monkey instead.
+    assert_eq!(compute_part2_required_monkey_number(&yells, "three"), Ok(3));
+    assert_eq!(
+        compute_part2_required_monkey_number(&yells, "nobody"),
+        Err(SolveError::NoSuchMonkey("nobody".to_string()))
+    );
+}
+
+#[test]
+fn unsolvable() {
+    let solve = |input| {
+        let yells = parse_monkey_yells(input).unwrap();
+        compute_part2_required_monkey_number(&yells, YOU_NAME)
+    };
+
+    assert_eq!(
+        solve("root: dbl + five\ndbl: humn * two\ntwo: 2\nfive: 5\nhumn: 1"),
+        Err(SolveError::NotInteger(
+            YOU_NAME.to_string(),
+            Rational::new(5, 2).unwrap()
+        ))
+    );
+    assert_eq!(
+        solve("root: sq + four\nsq: humn * humn\nfour: 4\nhumn: 1"),
+        Err(SolveError::NonLinear("sq".to_string()))
+    );
+    assert_eq!(
+        solve("root: inv + four\ninv: four / humn\nfour: 4\nhumn: 1"),
+        Err(SolveError::NonLinear("inv".to_string()))
+    );
+    assert_eq!(
+        solve("root: zero + four\nzero: humn - humn\nfour: 4\nhumn: 1"),
+        Err(SolveError::NoNumber)
+    );
+    assert_eq!(
+        solve("root: dbl + sum\ndbl: humn * two\nsum: humn + humn\ntwo: 2\nhumn: 1"),
+        Err(SolveError::AnyNumber)
+    );
+    assert_eq!(
+        solve("root: loop + four\nloop: four + loop\nfour: 4\nhumn: 1"),
+        Err(SolveError::Cycle("loop".to_string()))
+    );
+
+    let yells = parse_monkey_yells("root: four / three\nfour: 4\nthree: 3").unwrap();
+    assert_eq!(
+        compute_part1_root_monkey_number(&yells),
+        Err(SolveError::NotInteger(
+            "root".to_string(),
+            Rational::new(4, 3).unwrap()
+        ))
+    );
+
+    // The answers say why there's no number.
+    let yells = parse_monkey_yells("root: four + gone\nfour: 4\nhumn: 1").unwrap();
+    assert_eq!(Day21::part1(&yells), "no monkey named gone");
+    assert_eq!(Day21::part2(&yells), "no monkey named gone");
+    let yells = parse_monkey_yells("root: sq + four\nsq: humn * humn\nfour: 4\nhumn: 1").unwrap();
+    assert_eq!(Day21::part1(&yells), "5");
+    assert_eq!(
+        Day21::part2(&yells),
+        "sq's number isn't linear in the unknown"
+    );
+}
+
+#[test]
+fn dot() {
+    let input = "root: pppw + sjmn
+pppw: humn * two
+sjmn: 6
+two: 2
+humn: 5";
+    let yells = parse_monkey_yells(input).unwrap();
+
+    assert_eq!(
+        dependency_dot(&yells, YOU_NAME),
+        r#"digraph monkeys {
+    node [shape=box];
+    "root" [label="root\npppw + sjmn\n= 2 humn + 6", color=red, penwidth=2];
+    "root" -> "pppw" [color=red, penwidth=2];
+    "root" -> "sjmn";
+    "pppw" [label="pppw\nhumn * two\n= 2 humn", color=red, penwidth=2];
+    "pppw" -> "humn" [color=red, penwidth=2];
+    "pppw" -> "two";
+    "sjmn" [label="sjmn\n6"];
+    "two" [label="two\n2"];
+    "humn" [label="humn\n?", color=red, penwidth=2];
+}
+"#
+    );
+
+    // A monkey whose number can't be worked out is labeled with why, and so
+    // is every monkey listening to it, however many there are.
+    let input = "root: pppw + sjmn
+pppw: half + humn
+sjmn: half * two
+half: two / zero
+two: 2
+zero: 0
+humn: 5";
+    let yells = parse_monkey_yells(input).unwrap();
+    let dot = dependency_dot(&yells, YOU_NAME);
+    for name in ["root", "pppw", "sjmn", "half"] {
+        let label = dot
+            .lines()
+            .find(|line| line.starts_with(&format!("    {name:?} [label=")))
+            .unwrap();
+        assert!(label.contains("\\nhalf divides by zero\""), "{label}");
+    }
+}
@@ -0,0 +1,1053 @@
+use aoc_common::{parse, ParseError, Solution};
+use aoc_grid::Grid;
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Tile {
+    Absent,
+    Open,
+    Wall,
+}
+
+impl Tile {
+    fn symbol(self) -> char {
+        match self {
+            Self::Absent => ' ',
+            Self::Open => '.',
+            Self::Wall => '#',
+        }
+    }
+}
+
+impl Debug for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+type Dist = u32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Movement {
+    Forward(Dist),
+    Turn(Turn),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Horizontal {
+    Left,
+    Right,
+}
+
+impl Horizontal {
+    fn step(&self) -> isize {
+        match self {
+            Horizontal::Left => -1,
+            Horizontal::Right => 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Vertical {
+    Up,
+    Down,
+}
+
+impl Vertical {
+    fn step(&self) -> isize {
+        match self {
+            Vertical::Up => -1,
+            Vertical::Down => 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Direction {
+    Horizontal(Horizontal),
+    Vertical(Vertical),
+}
+
+impl Direction {
+    fn apply_turn(&self, turn: Turn) -> Direction {
+        match (turn, *self) {
+            (Turn::Left, Direction::Horizontal(Horizontal::Left)) => {
+                Direction::Vertical(Vertical::Down)
+            }
+            (Turn::Left, Direction::Horizontal(Horizontal::Right)) => {
+                Direction::Vertical(Vertical::Up)
+            }
+
+            (Turn::Left, Direction::Vertical(Vertical::Up)) => {
+                Direction::Horizontal(Horizontal::Left)
+            }
+
+            (Turn::Left, Direction::Vertical(Vertical::Down)) => {
+                Direction::Horizontal(Horizontal::Right)
+            }
+
+            (Turn::Right, Direction::Horizontal(Horizontal::Left)) => {
+                Direction::Vertical(Vertical::Up)
+            }
+            (Turn::Right, Direction::Horizontal(Horizontal::Right)) => {
+                Direction::Vertical(Vertical::Down)
+            }
+
+            (Turn::Right, Direction::Vertical(Vertical::Up)) => {
+                Direction::Horizontal(Horizontal::Right)
+            }
+            (Turn::Right, Direction::Vertical(Vertical::Down)) => {
+                Direction::Horizontal(Horizontal::Left)
+            }
+        }
+    }
+
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Horizontal(h) => (h.step(), 0),
+            Direction::Vertical(v) => (0, v.step()),
+        }
+    }
+
+    fn arrow(self) -> char {
+        match self {
+            Direction::Vertical(Vertical::Up) => '^',
+            Direction::Vertical(Vertical::Down) => 'v',
+            Direction::Horizontal(Horizontal::Left) => '<',
+            Direction::Horizontal(Horizontal::Right) => '>',
+        }
+    }
+
+    fn to_number(self) -> u8 {
+        match self {
+            Direction::Vertical(Vertical::Up) => 3,
+            Direction::Vertical(Vertical::Down) => 1,
+            Direction::Horizontal(Horizontal::Left) => 2,
+            Direction::Horizontal(Horizontal::Right) => 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Folding<'a> {
+    Unfolded,
+    Cube(&'a Cube),
+}
+
+pub struct BoardMap {
+    tiles: Grid<Tile>,
+}
+
+impl Debug for BoardMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self.tiles.rows() {
+            write!(f, "{line:?}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BoardMap {
+    fn new(s: &str) -> Result<BoardMap, ParseError> {
+        let tiles = Grid::parse_ragged(s, Tile::Absent, "a tile", |c| match c {
+            ' ' => Some(Tile::Absent),
+            '.' => Some(Tile::Open),
+            '#' => Some(Tile::Wall),
+            _ => None,
+        })?;
+
+        Ok(BoardMap { tiles })
+    }
+
+    fn tile_at(&self, x: usize, y: usize) -> Tile {
+        self.tiles[(x, y)]
+    }
+
+    fn step(
+        &self,
+        x: usize,
+        y: usize,
+        dir: Direction,
+        folding: Folding<'_>,
+    ) -> ((usize, usize), Direction) {
+        let offset = dir.offset();
+
+        match folding {
+            // Without folding, walk straight on, wrapping around the edges of
+            // the board.  Walking off the sides is handled by the caller.
+            Folding::Unfolded => (self.tiles.offset_wrapping((x, y), offset), dir),
+
+            // Folded into a cube, walking off a side leads onto whichever side
+            // is folded up against it.
+            Folding::Cube(cube) => match self.tiles.offset((x, y), offset) {
+                Some(pos) if self.tiles[pos] != Tile::Absent => (pos, dir),
+                _ => cube.wrap((x, y), dir),
+            },
+        }
+    }
+
+    fn move_one(
+        &self,
+        x: usize,
+        y: usize,
+        dir: Direction,
+        folding: Folding<'_>,
+    ) -> Option<((usize, usize), Direction)> {
+        assert!(self.tile_at(x, y) == Tile::Open, "x/y must be valid");
+
+        let (mut current_x, mut current_y, mut current_dir) = (x, y, dir);
+        loop {
+            let ((cand_x, cand_y), cand_dir) =
+                self.step(current_x, current_y, current_dir, folding);
+            match self.tile_at(cand_x, cand_y) {
+                Tile::Wall => return None,
+                Tile::Open => return Some(((cand_x, cand_y), cand_dir)),
+                Tile::Absent => {
+                    ((current_x, current_y), current_dir) = ((cand_x, cand_y), cand_dir)
+                }
+            }
+        }
+    }
+
+    fn compute_move(
+        &self,
+        (start_x, start_y): (usize, usize),
+        dir: Direction,
+        dist: Dist,
+        folding: Folding<'_>,
+        visit: &mut dyn FnMut((usize, usize), Direction),
+    ) -> ((usize, usize), Direction) {
+        assert!(
+            self.tile_at(start_x, start_y) == Tile::Open,
+            "must have valid starting position"
+        );
+
+        //println!("computing move from (({start_x}, {start_y}), {dir:?}) by {dist}");
+
+        let (mut pos_x, mut pos_y) = (start_x, start_y);
+        let mut curr_dir = dir;
+        for _ in 0..dist {
+            if let Some(((new_x, new_y), new_dir)) = self.move_one(pos_x, pos_y, curr_dir, folding)
+            {
+                (pos_x, pos_y) = (new_x, new_y);
+                curr_dir = new_dir;
+                visit((pos_x, pos_y), curr_dir);
+            } else {
+                break;
+            }
+            //println!("moved to ({pos_x}, {pos_y})...");
+        }
+
+        //println!("move complete");
+        ((pos_x, pos_y), curr_dir)
+    }
+
+    fn at_start(&self) -> ((usize, usize), Direction) {
+        let x = self
+            .tiles
+            .row(0)
+            .iter()
+            .enumerate()
+            .find(|(_i, &tile)| tile == Tile::Open)
+            .expect("must have open tile")
+            .0;
+        ((x, 0), Direction::Horizontal(Horizontal::Right))
+    }
+}
+
+fn parse_board_map(s: &str) -> Result<BoardMap, ParseError> {
+    BoardMap::new(s)
+}
+
+/// A direction in the space the board is folded up in.
+type Vector = [i64; 3];
+
+fn negate(v: Vector) -> Vector {
+    v.map(|c| -c)
+}
+
+fn dot(a: Vector, b: Vector) -> i64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Horizontal(Horizontal::Right),
+    Direction::Vertical(Vertical::Down),
+    Direction::Horizontal(Horizontal::Left),
+    Direction::Vertical(Vertical::Up),
+];
+
+/// One side of the cube: where it is on the board, and which way it faces
+/// once the board is folded.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    /// The board position of its top left tile.
+    origin: (usize, usize),
+    /// The direction it faces, out from the cube.
+    normal: Vector,
+    /// The directions walking right and down across it go.
+    right: Vector,
+    down: Vector,
+}
+
+impl Face {
+    fn vector(&self, dir: Direction) -> Vector {
+        match dir {
+            Direction::Horizontal(Horizontal::Right) => self.right,
+            Direction::Horizontal(Horizontal::Left) => negate(self.right),
+            Direction::Vertical(Vertical::Down) => self.down,
+            Direction::Vertical(Vertical::Up) => negate(self.down),
+        }
+    }
+
+    fn direction(&self, v: Vector) -> Direction {
+        DIRECTIONS
+            .into_iter()
+            .find(|&dir| self.vector(dir) == v)
+            .expect("direction along the face")
+    }
+
+    /// The side at `origin`, across this side's edge in direction `dir`,
+    /// oriented as it is once folded.  Walking off this side onto it, you
+    /// walk down the cube, away from the way this side faces.
+    fn neighbor(&self, dir: Direction, origin: (usize, usize)) -> Face {
+        let (n, r, d) = (self.normal, self.right, self.down);
+        let (normal, right, down) = match dir {
+            Direction::Horizontal(Horizontal::Right) => (r, negate(n), d),
+            Direction::Horizontal(Horizontal::Left) => (negate(r), n, d),
+            Direction::Vertical(Vertical::Down) => (d, r, negate(n)),
+            Direction::Vertical(Vertical::Up) => (negate(d), r, n),
+        };
+        Face {
+            origin,
+            normal,
+            right,
+            down,
+        }
+    }
+
+    /// Where the tile `(i, j)` across from the side's top left lies, in a
+    /// cube centered on the origin with sides `2 * side_width` wide (so that
+    /// the tile centers are all at integer coordinates).
+    fn position(&self, (i, j): (usize, usize), side_width: usize) -> Vector {
+        let s = side_width as i64;
+        let (i, j) = (2 * i as i64 + 1 - s, 2 * j as i64 + 1 - s);
+        [0, 1, 2].map(|k| self.normal[k] * s + self.right[k] * i + self.down[k] * j)
+    }
+
+    /// The tile across from the side's top left at `position` on the cube.
+    fn tile(&self, position: Vector, side_width: usize) -> (usize, usize) {
+        let s = side_width as i64;
+        let coord = |axis| ((dot(position, axis) + s - 1) / 2) as usize;
+        (coord(self.right), coord(self.down))
+    }
+}
+
+/// The board folded up into a cube.
+pub struct Cube {
+    side_width: usize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    /// Fold up the board parsed from `s`, which must be a net of a cube: six
+    /// equal squares, joined edge to edge, that fold up without overlapping.
+    /// Any of the eleven such nets, at any size, will do.
+    fn fold(board: &BoardMap, s: &str) -> Result<Cube, ParseError> {
+        let not_a_net = || ParseError::new(s, "a board that folds into a cube");
+
+        let area = board.tiles.iter().filter(|&&t| t != Tile::Absent).count();
+        let side_width = (1..)
+            .take_while(|w| 6 * w * w <= area)
+            .find(|w| 6 * w * w == area)
+            .ok_or_else(not_a_net)?;
+
+        let (width, height) = (board.tiles.width(), board.tiles.height());
+        if width % side_width != 0 || height % side_width != 0 {
+            return Err(not_a_net());
+        }
+
+        // Which squares of side width, across and down the board, are sides
+        // of the cube.  Each must be entirely present or entirely absent.
+        let is_side = |(sx, sy): (usize, usize)| {
+            let present = (0..side_width)
+                .flat_map(|j| (0..side_width).map(move |i| (i, j)))
+                .filter(|&(i, j)| {
+                    board.tile_at(sx * side_width + i, sy * side_width + j) != Tile::Absent
+                })
+                .count();
+            match present {
+                0 => Ok(false),
+                n if n == side_width * side_width => Ok(true),
+                _ => Err(not_a_net()),
+            }
+        };
+        let sides = Grid::from_vec(
+            width / side_width,
+            (0..height / side_width)
+                .flat_map(|sy| (0..width / side_width).map(move |sx| (sx, sy)))
+                .map(is_side)
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+
+        // Fold up the sides, working out from the first one.
+        let first = sides.position(|&side| side).ok_or_else(not_a_net)?;
+        let mut folded: Grid<Option<Face>> = sides.map(|_| None);
+        folded[first] = Some(Face {
+            origin: (first.0 * side_width, first.1 * side_width),
+            normal: [0, 0, -1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        });
+        let mut faces = vec![];
+        let mut pending = vec![first];
+        while let Some(pos) = pending.pop() {
+            let face = folded[pos].expect("folded");
+            faces.push(face);
+            for dir in DIRECTIONS {
+                let Some(next) = sides.offset(pos, dir.offset()) else {
+                    continue;
+                };
+                if sides[next] && folded[next].is_none() {
+                    let origin = (next.0 * side_width, next.1 * side_width);
+                    folded[next] = Some(face.neighbor(dir, origin));
+                    pending.push(next);
+                }
+            }
+        }
+
+        // The sides must all be joined up, each on a different side of the
+        // cube.
+        if faces.len() != 6 {
+            return Err(not_a_net());
+        }
+        let mut normals = faces.iter().map(|face| face.normal).collect::<Vec<_>>();
+        normals.sort();
+        normals.dedup();
+        if normals.len() != 6 {
+            return Err(not_a_net());
+        }
+
+        Ok(Cube { side_width, faces })
+    }
+
+    fn face_at(&self, (x, y): (usize, usize)) -> &Face {
+        let w = self.side_width;
+        self.faces
+            .iter()
+            .find(|face| (face.origin.0 / w, face.origin.1 / w) == (x / w, y / w))
+            .expect("position on a side")
+    }
+
+    /// Where walking `dir` off the edge of the side at `(x, y)` leads, and
+    /// which way walking continues from there.
+    fn wrap(&self, (x, y): (usize, usize), dir: Direction) -> ((usize, usize), Direction) {
+        let from = self.face_at((x, y));
+        let v = from.vector(dir);
+        let to = self
+            .faces
+            .iter()
+            .find(|face| face.normal == v)
+            .expect("a side facing every way");
+
+        // Step off the edge of one side and over onto the next.
+        let position = from.position((x - from.origin.0, y - from.origin.1), self.side_width);
+        let position = [0, 1, 2].map(|k| position[k] - from.normal[k] + v[k]);
+
+        let (i, j) = to.tile(position, self.side_width);
+        (
+            (to.origin.0 + i, to.origin.1 + j),
+            to.direction(negate(from.normal)),
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct MoveIter<'a> {
+    remaining: Peekable<Chars<'a>>,
+}
+
+impl<'a> MoveIter<'a> {
+    fn new(desc: &'a str) -> MoveIter<'a> {
+        MoveIter {
+            remaining: desc.chars().peekable(),
+        }
+    }
+}
+
+impl Iterator for MoveIter<'_> {
+    type Item = Movement;
+
+    fn next(&mut self) -> Option<Movement> {
+        let c = self.remaining.next()?;
+        if c == 'L' {
+            return Some(Movement::Turn(Turn::Left));
+        }
+        if c == 'R' {
+            return Some(Movement::Turn(Turn::Right));
+        }
+
+        let mut dist = c.to_digit(10).unwrap();
+        while let Some(c) = self.remaining.peek().and_then(|c| c.to_digit(10)) {
+            self.remaining.next().unwrap();
+            dist = dist * 10 + c;
+        }
+        Some(Movement::Forward(dist))
+    }
+}
+
+fn parse_path_description(desc: &str) -> Result<MoveIter<'_>, ParseError> {
+    // Check the description up front, so that iterating it can't fail.
+    if let Some(i) = desc.find(|c: char| !matches!(c, 'L' | 'R' | '0'..='9')) {
+        return Err(ParseError::new(&desc[i..], "L, R, or a distance"));
+    }
+    Ok(MoveIter::new(desc))
+}
+
+/// The board, the cube it folds into (or why it doesn't, which only matters
+/// when walking around the cube), and the path.
+pub type Notes<'a> = (BoardMap, Result<Cube, ParseError>, MoveIter<'a>);
+
+fn parse_input(input: &str) -> Result<Notes<'_>, ParseError> {
+    let (board_str, path_desc) = parse::split_once(input, "\n\n")?;
+
+    let board_map = parse_board_map(board_str)?;
+    let cube = Cube::fold(&board_map, board_str).map_err(|error| error.locate(input));
+    Ok((board_map, cube, parse_path_description(path_desc.trim())?))
+}
+
+#[test]
+fn boards() {
+    assert_eq!(
+        parse_board_map(
+            "  ..#.
+  #.
+  ..
+  ##
+  .#
+  ..
+#..#
+..#.
+"
+        )
+        .unwrap()
+        .tiles,
+        Grid::from_rows(vec![
+            vec![
+                Tile::Absent,
+                Tile::Absent,
+                Tile::Open,
+                Tile::Open,
+                Tile::Wall,
+                Tile::Open
+            ],
+            vec![
+                Tile::Absent,
+                Tile::Absent,
+                Tile::Wall,
+                Tile::Open,
+                Tile::Absent,
+                Tile::Absent
+            ],
+            vec![
+                Tile::Absent,
+                Tile::Absent,
+                Tile::Open,
+                Tile::Open,
+                Tile::Absent,
+                Tile::Absent
+            ],
+            vec![
+                Tile::Absent,
+                Tile::Absent,
+                Tile::Wall,
+                Tile::Wall,
+                Tile::Absent,
+                Tile::Absent
+            ],
+            vec![
+                Tile::Absent,
+                Tile::Absent,
+                Tile::Open,
+                Tile::Wall,
+                Tile::Absent,
+                Tile::Absent
+            ],
+            vec![
+                Tile::Absent,
+                Tile::Absent,
+                Tile::Open,
+                Tile::Open,
+                Tile::Absent,
+                Tile::Absent
+            ],
+            vec![
+                Tile::Wall,
+                Tile::Open,
+                Tile::Open,
+                Tile::Wall,
+                Tile::Absent,
+                Tile::Absent
+            ],
+            vec![
+                Tile::Open,
+                Tile::Open,
+                Tile::Wall,
+                Tile::Open,
+                Tile::Absent,
+                Tile::Absent
+            ],
+        ])
+    );
+}
+
+#[test]
+fn paths() {
+    assert_eq!(
+        parse_path_description("1L203R3L")
+            .unwrap()
+            .collect::<Vec<_>>(),
+        vec![
+            Movement::Forward(1),
+            Movement::Turn(Turn::Left),
+            Movement::Forward(203),
+            Movement::Turn(Turn::Right),
+            Movement::Forward(3),
+            Movement::Turn(Turn::Left),
+        ]
+    );
+}
+
+fn find_final_position(
+    board_map: &BoardMap,
+    path: impl Iterator<Item = Movement>,
+    folding: Folding<'_>,
+) -> ((usize, usize), Direction) {
+    let ((mut x, mut y), mut dir) = board_map.at_start();
+
+    for movement in path {
+        match movement {
+            Movement::Forward(dist) => {
+                ((x, y), dir) = board_map.compute_move((x, y), dir, dist, folding, &mut |_, _| {});
+            }
+            Movement::Turn(turn) => dir = dir.apply_turn(turn),
+        };
+    }
+
+    ((x, y), dir)
+}
+
+/// The board with the trail walked across it, each tile marked with the way
+/// it was last faced.
+struct Trail<'a> {
+    board_map: &'a BoardMap,
+    facings: Grid<Option<Direction>>,
+}
+
+impl Trail<'_> {
+    fn new(board_map: &BoardMap) -> Trail<'_> {
+        Trail {
+            board_map,
+            facings: board_map.tiles.map(|_| None),
+        }
+    }
+
+    fn mark(&mut self, pos: (usize, usize), dir: Direction) {
+        self.facings[pos] = Some(dir);
+    }
+
+    fn render(&self) -> String {
+        let mut s = String::new();
+        for (y, row) in self.board_map.tiles.rows().enumerate() {
+            let line = row
+                .iter()
+                .enumerate()
+                .map(|(x, tile)| match self.facings[(x, y)] {
+                    Some(dir) => dir.arrow(),
+                    None => tile.symbol(),
+                })
+                .collect::<String>();
+            s.push_str(line.trim_end());
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// How long each frame of a replay is usually shown.
+pub const FRAME: Duration = Duration::from_millis(20);
+
+/// Clear `screen` and draw `trail` on it from the top left, under `caption`,
+/// then leave it there for `frame`.
+fn draw_frame(
+    screen: &mut dyn Write,
+    trail: &Trail<'_>,
+    caption: &str,
+    frame: Duration,
+) -> io::Result<()> {
+    write!(screen, "\x1b[2J\x1b[H")?;
+    writeln!(screen, "{caption}")?;
+    write!(screen, "{}", trail.render())?;
+    screen.flush()?;
+    thread::sleep(frame);
+    Ok(())
+}
+
+/// Walk `path` across the board, folded into `cube` if one's given, and
+/// write the board with the trail walked across it, then the password, to
+/// `board`.  If there's a `screen` (a terminal), animate the walk on it,
+/// redrawing the board after every step and turn and showing each drawing
+/// for `frame`.
+pub fn replay(
+    board_map: &BoardMap,
+    cube: Option<&Cube>,
+    path: MoveIter<'_>,
+    mut screen: Option<&mut dyn Write>,
+    frame: Duration,
+    board: &mut impl Write,
+) -> io::Result<()> {
+    let folding = match cube {
+        Some(cube) => Folding::Cube(cube),
+        None => Folding::Unfolded,
+    };
+
+    let mut trail = Trail::new(board_map);
+    let ((mut x, mut y), mut dir) = board_map.at_start();
+    trail.mark((x, y), dir);
+
+    for (i, movement) in path.enumerate() {
+        let caption = format!("movement {}: {movement:?}", i + 1);
+        match movement {
+            Movement::Forward(dist) => {
+                let mut drawn = Ok(());
+                let mut step = 0;
+                ((x, y), dir) =
+                    board_map.compute_move((x, y), dir, dist, folding, &mut |pos, dir| {
+                        trail.mark(pos, dir);
+                        step += 1;
+                        if let (Some(screen), Ok(())) = (screen.as_deref_mut(), &drawn) {
+                            drawn = draw_frame(
+                                screen,
+                                &trail,
+                                &format!("{caption}, step {step}"),
+                                frame,
+                            );
+                        }
+                    });
+                drawn?;
+            }
+            Movement::Turn(turn) => {
+                dir = dir.apply_turn(turn);
+                trail.mark((x, y), dir);
+                if let Some(screen) = screen.as_deref_mut() {
+                    draw_frame(screen, &trail, &caption, frame)?;
+                }
+            }
+        }
+    }
+
+    write!(board, "{}", trail.render())?;
+    writeln!(board, "password: {}", compute_password(x, y, dir))
+}
+
+fn compute_password(x: usize, y: usize, dir: Direction) -> u32 {
+    (1000 * (y + 1)) as u32 + (4 * (x + 1)) as u32 + dir.to_number() as u32
+}
+
+#[cfg(test)]
+static EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+#[test]
+fn example() {
+    let (board_map, _cube, path) = parse_input(EXAMPLE).unwrap();
+
+    // Part 1.
+    {
+        let ((x, y), dir) = find_final_position(&board_map, path.clone(), Folding::Unfolded);
+        println!("x: {x}, y: {y}, dir: {dir:?}");
+        assert_eq!(
+            (x + 1, y + 1, dir),
+            (8, 6, Direction::Horizontal(Horizontal::Right)),
+        );
+
+        let password = compute_password(x, y, dir);
+        println!("Password: {password}");
+        assert_eq!(password, 6_032);
+    }
+}
+
+#[test]
+fn example_cube() {
+    let (board_map, cube, path) = parse_input(EXAMPLE).unwrap();
+    let cube = cube.unwrap();
+    assert_eq!(cube.side_width, 4);
+
+    // Part 2.
+    {
+        let ((x, y), dir) = find_final_position(&board_map, path, Folding::Cube(&cube));
+        println!("x: {x}, y: {y}, dir: {dir:?}");
+        assert_eq!(
+            (x + 1, y + 1, dir),
+            (7, 5, Direction::Vertical(Vertical::Up)),
+        );
+
+        let password = compute_password(x, y, dir);
+        println!("Password: {password}");
+        assert_eq!(password, 5_031);
+    }
+}
+
+/// The eleven nets of a cube, as the positions of their sides in units of
+/// the side width.
+#[cfg(test)]
+const NETS: [[(usize, usize); 6]; 11] = [
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (0, 2)],
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)],
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)],
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (3, 2)],
+    [(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)],
+    [(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)],
+    [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (1, 2)],
+    [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (2, 2)],
+    [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (3, 2)],
+    [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2)],
+    [(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)],
+];
+
+/// A board of open tiles laid out with sides at `positions`, after mirroring
+/// and rotating it by `symmetry` (one of eight).
+#[cfg(test)]
+fn net_board(positions: &[(usize, usize)], symmetry: u8, side_width: usize) -> String {
+    let positions = positions
+        .iter()
+        .map(|&(x, y)| {
+            let (x, y) = if symmetry & 1 != 0 { (y, x) } else { (x, y) };
+            let x = if symmetry & 2 != 0 { 4 - x } else { x };
+            let y = if symmetry & 4 != 0 { 4 - y } else { y };
+            (x as isize, y as isize)
+        })
+        .collect::<Vec<_>>();
+    let min_x = positions.iter().map(|p| p.0).min().unwrap();
+    let min_y = positions.iter().map(|p| p.1).min().unwrap();
+    let positions = positions
+        .iter()
+        .map(|&(x, y)| ((x - min_x) as usize, (y - min_y) as usize))
+        .collect::<Vec<_>>();
+
+    let width = positions.iter().map(|p| p.0).max().unwrap() + 1;
+    let height = positions.iter().map(|p| p.1).max().unwrap() + 1;
+    let mut board = String::new();
+    for y in 0..height * side_width {
+        for x in 0..width * side_width {
+            let side = (x / side_width, y / side_width);
+            board.push(if positions.contains(&side) { '.' } else { ' ' });
+        }
+        board.push('\n');
+    }
+    board
+}
+
+#[test]
+fn nets() {
+    let turn_around = |dir: Direction| dir.apply_turn(Turn::Left).apply_turn(Turn::Left);
+
+    for net in NETS {
+        for symmetry in 0..8 {
+            for side_width in 1..=4 {
+                let board = net_board(&net, symmetry, side_width);
+                let board_map = parse_board_map(&board).unwrap();
+                let cube = Cube::fold(&board_map, &board).unwrap();
+                assert_eq!(cube.side_width, side_width);
+                let folding = Folding::Cube(&cube);
+
+                for (x, y) in board_map.tiles.positions() {
+                    if board_map.tile_at(x, y) == Tile::Absent {
+                        continue;
+                    }
+
+                    for dir in DIRECTIONS {
+                        // Stepping forward and then back returns to the start.
+                        let ((bx, by), back) = board_map.step(x, y, dir, folding);
+                        let ((rx, ry), returned) =
+                            board_map.step(bx, by, turn_around(back), folding);
+                        assert_eq!(((rx, ry), turn_around(returned)), ((x, y), dir));
+
+                        // Walking straight on around the cube returns to the
+                        // start too.
+                        let (mut pos, mut walking) = ((x, y), dir);
+                        for _ in 0..4 * side_width {
+                            (pos, walking) = board_map.step(pos.0, pos.1, walking, folding);
+                        }
+                        assert_eq!((pos, walking), ((x, y), dir), "{board}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn not_nets() {
+    for board in [
+        // Too few sides.
+        "..\n..\n",
+        // Sides that overlap when folded.
+        "......\n",
+        "...\n...\n",
+        // Sides that aren't joined up.
+        ". .\n  .\n. .\n.  \n",
+        // Part of a side missing.
+        " .....\n......\n",
+    ] {
+        let board_map = parse_board_map(board).unwrap();
+        assert!(Cube::fold(&board_map, board).is_err(), "{board:?}");
+    }
+}
+
+#[test]
+fn flat_boards() {
+    // A board that isn't a cube's net can still be walked around flat.
+    let input = "...\n.#.\n...\n\n2R2R1";
+    let notes = Day22::parse(input).unwrap();
+    assert!(notes.1.is_err());
+    assert_eq!(Day22::part1(&notes), "3010");
+    assert!(Day22::part2(&notes).starts_with("the board doesn't fold into a cube: line 1"));
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 22;
+
+    type Parsed<'a> = Notes<'a>;
+
+    fn parse(input: &str) -> Result<Notes<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((board_map, _cube, path): &Notes<'_>) -> String {
+        let ((x, y), dir) = find_final_position(board_map, path.clone(), Folding::Unfolded);
+        compute_password(x, y, dir).to_string()
+    }
+
+    fn part2((board_map, cube, path): &Notes<'_>) -> String {
+        let cube = match cube {
+            Ok(cube) => cube,
+            Err(error) => return format!("the board doesn't fold into a cube: {error}"),
+        };
+        let ((x, y), dir) = find_final_position(board_map, path.clone(), Folding::Cube(cube));
+        compute_password(x, y, dir).to_string()
+    }
+}
+
+#[test]
+fn replaying() {
+    let (board_map, cube, path) = parse_input(EXAMPLE).unwrap();
+
+    let mut out = vec![];
+    replay(
+        &board_map,
+        None,
+        path.clone(),
+        None,
+        Duration::ZERO,
+        &mut out,
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "        >>v#
+        .#v.
+        #.v.
+        ..v.
+...#...v..v#
+>>>v...>#.>>
+..#v...#....
+...>>>>v..#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+password: 6032
+"
+    );
+
+    let mut out = vec![];
+    replay(
+        &board_map,
+        Some(&cube.unwrap()),
+        path.clone(),
+        None,
+        Duration::ZERO,
+        &mut out,
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "        >>v#
+        .#v.
+        #.v.
+        ..v.
+...#..^...v#
+.>>>>>^.#.>>
+.^#....#....
+.^........#.
+        ...#..v.
+        .....#v.
+        .#v<<<<.
+        ..v...#.
+password: 5031
+"
+    );
+
+    // Animated, each step and turn is a frame of its own, the last of them
+    // showing the whole trail.
+    let (mut screen, mut out) = (vec![], vec![]);
+    replay(
+        &board_map,
+        None,
+        path,
+        Some(&mut screen),
+        Duration::ZERO,
+        &mut out,
+    )
+    .unwrap();
+    let screen = String::from_utf8(screen).unwrap();
+    let frames = screen.split("\x1b[2J\x1b[H").skip(1).collect::<Vec<_>>();
+    assert!(frames[0].starts_with("movement 1: Forward(10), step 1\n        >>.#\n"));
+    assert!(frames[1].starts_with("movement 1: Forward(10), step 2\n        >>>#\n"));
+    assert!(frames[2].starts_with("movement 2: Turn(Right)\n"));
+    let out = String::from_utf8(out).unwrap();
+    let last = frames.last().unwrap();
+    assert_eq!(
+        last[last.find('\n').unwrap() + 1..],
+        out[..out.find("password").unwrap()]
+    );
+}
@@ -0,0 +1,110 @@
+use aoc_common::{parse, ParseError, Solution};
+
+/// The fewest cubes of each color a game could have been played with.
+pub struct RequiredAmount {
+    game_num: usize,
+    red: usize,
+    green: usize,
+    blue: usize,
+}
+
+fn parse_game(line: &str) -> Result<RequiredAmount, ParseError> {
+    let rest = parse::strip_prefix(line, "Game ")?;
+    let (game_num, reveals) = parse::split_once(rest, ": ")?;
+    let game_num = parse::value(game_num, "a game number")?;
+
+    let mut red = 0;
+    let mut green = 0;
+    let mut blue = 0;
+
+    for game in reveals.split("; ") {
+        for color_count in game.split(", ") {
+            let (num, color) = parse::split_once(color_count, " ")?;
+            let num = parse::value::<usize>(num, "a count")?;
+
+            match color {
+                "red" => red = red.max(num),
+                "green" => green = green.max(num),
+                "blue" => blue = blue.max(num),
+                _ => return Err(ParseError::new(color, "red, green, or blue")),
+            }
+        }
+    }
+
+    Ok(RequiredAmount {
+        game_num,
+        red,
+        green,
+        blue,
+    })
+}
+
+fn parse_games(input: &str) -> Result<Vec<RequiredAmount>, ParseError> {
+    input.lines().map(parse_game).collect()
+}
+
+fn sum_possible_games(games: &[RequiredAmount]) -> usize {
+    games
+        .iter()
+        .filter_map(|gi| {
+            if gi.red <= 12 && gi.green <= 13 && gi.blue <= 14 {
+                Some(gi.game_num)
+            } else {
+                None
+            }
+        })
+        .sum::<usize>()
+}
+
+fn sum_of_powers(games: &[RequiredAmount]) -> usize {
+    games
+        .iter()
+        .map(|gi| gi.red * gi.green * gi.blue)
+        .sum::<usize>()
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    let games = parse_games(INPUT).unwrap();
+
+    // Part 1.
+    println!("Part 1:");
+    let sum_possible_games = sum_possible_games(&games);
+    println!("Sum: {sum_possible_games}");
+    assert_eq!(sum_possible_games, 8);
+
+    // Part 2.
+    println!("Part 2:");
+    let sum_of_powers = sum_of_powers(&games);
+    println!("Sum of powers: {sum_of_powers}");
+    assert_eq!(sum_of_powers, 2_286);
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+
+    type Parsed<'a> = Vec<RequiredAmount>;
+
+    fn parse(input: &str) -> Result<Vec<RequiredAmount>, ParseError> {
+        parse_games(input)
+    }
+
+    fn part1(games: &Vec<RequiredAmount>) -> String {
+        sum_possible_games(games).to_string()
+    }
+
+    fn part2(games: &Vec<RequiredAmount>) -> String {
+        sum_of_powers(games).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day02);
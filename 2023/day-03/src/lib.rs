@@ -0,0 +1,210 @@
+use aoc_common::{ParseError, Solution};
+use aoc_grid::Grid;
+
+fn parse_schematic(s: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(s, "a schematic character", Some)
+}
+
+/// The `(y, x)` locations of every symbol in the schematic.
+fn symbols(grid: &Grid<char>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    grid.cells()
+        .filter(|(_, &c)| is_symbol(c))
+        .map(|((x, y), _)| (y, x))
+}
+
+fn is_symbol(c: char) -> bool {
+    !matches!(c, '0'..='9' | '.')
+}
+
+fn sum_numbers_by_symbols(grid: &Grid<char>) -> u32 {
+    let mut sum = 0;
+
+    for y in 0..grid.height() {
+        let mut x = 0;
+        while x < grid.width() {
+            match grid[(x, y)] {
+                '.' => {}
+                '0'..='9' => {
+                    let begin = x;
+                    while x < grid.width() && grid[(x, y)].is_ascii_digit() {
+                        x += 1;
+                    }
+
+                    let end = x - 1;
+
+                    let before_x = begin.saturating_sub(1);
+                    let after_x = (end + 1).min(grid.width() - 1);
+
+                    if (y > 0 && (before_x..=after_x).any(|x| is_symbol(grid[(x, y - 1)])))
+                        || (y < grid.height() - 1
+                            && (before_x..=after_x).any(|x| is_symbol(grid[(x, y + 1)])))
+                        || (begin > 0 && is_symbol(grid[(begin - 1, y)]))
+                        || (end < grid.width() - 1 && is_symbol(grid[(end + 1, y)]))
+                    {
+                        let mut n = 0;
+                        for x in begin..=end {
+                            n *= 10;
+                            n += grid[(x, y)].to_digit(10).expect("digit");
+                        }
+
+                        sum += n;
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+
+            x += 1;
+        }
+    }
+
+    sum
+}
+
+fn number_at(grid: &Grid<char>, y: usize, x: usize) -> u32 {
+    let mut start = x;
+    assert!(grid[(x, y)].is_ascii_digit());
+
+    while start > 0 && grid[(start - 1, y)].is_ascii_digit() {
+        start -= 1;
+    }
+    let mut end = x;
+    while end < grid.width() - 1 && grid[(end + 1, y)].is_ascii_digit() {
+        end += 1;
+    }
+
+    let mut n = 0;
+    for x in start..=end {
+        n *= 10;
+        n += grid[(x, y)].to_digit(10).expect("digit");
+    }
+
+    n
+}
+
+fn sum_gear_ratios(grid: &Grid<char>) -> u32 {
+    let mut sum = 0;
+    for (y, x) in symbols(grid) {
+        if grid[(x, y)] != '*' {
+            continue;
+        }
+
+        let mut number_positions = vec![];
+
+        if y > 0 {
+            let above_left = (x > 0 && grid[(x - 1, y - 1)].is_ascii_digit()) as usize;
+            let above = grid[(x, y - 1)].is_ascii_digit() as usize;
+            let above_right =
+                (x < grid.width() - 1 && grid[(x + 1, y - 1)].is_ascii_digit()) as usize;
+
+            let sum = above_left + above + above_right;
+            if sum == 0 {
+                // Do nothing.
+            } else if sum == 2 {
+                if above == 0 {
+                    number_positions.push((y - 1, x - 1));
+                    number_positions.push((y - 1, x + 1));
+                } else {
+                    number_positions.push((y - 1, x));
+                }
+            } else if sum == 3 || above == 1 {
+                number_positions.push((y - 1, x));
+            } else if above_left == 1 {
+                number_positions.push((y - 1, x - 1));
+            } else {
+                assert_eq!(above_right, 1);
+                number_positions.push((y - 1, x + 1));
+            }
+        }
+
+        if x > 0 && grid[(x - 1, y)].is_ascii_digit() {
+            number_positions.push((y, x - 1));
+        }
+        if x < grid.width() - 1 && grid[(x + 1, y)].is_ascii_digit() {
+            number_positions.push((y, x + 1));
+        }
+
+        if y < grid.height() - 1 {
+            let below_left = (x > 0 && grid[(x - 1, y + 1)].is_ascii_digit()) as usize;
+            let below = grid[(x, y + 1)].is_ascii_digit() as usize;
+            let below_right =
+                (x < grid.width() - 1 && grid[(x + 1, y + 1)].is_ascii_digit()) as usize;
+
+            let sum = below_left + below + below_right;
+            if sum == 0 {
+                // Do nothing.
+            } else if sum == 2 {
+                if below == 0 {
+                    number_positions.push((y + 1, x - 1));
+                    number_positions.push((y + 1, x + 1));
+                } else {
+                    number_positions.push((y + 1, x));
+                }
+            } else if sum == 3 || below == 1 {
+                number_positions.push((y + 1, x));
+            } else if below_left == 1 {
+                number_positions.push((y + 1, x - 1));
+            } else {
+                assert_eq!(below_right, 1);
+                number_positions.push((y + 1, x + 1));
+            }
+        }
+
+        if number_positions.len() == 2 {
+            sum += number_positions
+                .iter()
+                .fold(1, |acc, (y, x)| acc * number_at(grid, *y, *x));
+        }
+    }
+
+    sum
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    let grid = parse_schematic(INPUT).unwrap();
+
+    // Part 1.
+    let sum = sum_numbers_by_symbols(&grid);
+    println!("Sum of numbers by symbols: {sum}");
+    assert_eq!(sum, 4361);
+
+    // Part 2.
+    let sum_gear_ratios = sum_gear_ratios(&grid);
+    println!("Sum of numbers by gears: {sum_gear_ratios}");
+    assert_eq!(sum_gear_ratios, 467_835);
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+
+    type Parsed<'a> = Grid<char>;
+
+    fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        parse_schematic(input)
+    }
+
+    fn part1(grid: &Grid<char>) -> String {
+        sum_numbers_by_symbols(grid).to_string()
+    }
+
+    fn part2(grid: &Grid<char>) -> String {
+        sum_gear_ratios(grid).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day03);
@@ -0,0 +1,120 @@
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::HashSet;
+
+struct Card {
+    winning_numbers: HashSet<u8>,
+    have_numbers: HashSet<u8>,
+}
+
+impl Card {
+    fn new(s: &str) -> Result<Card, ParseError> {
+        let (_, rest) = parse::split_once(s, ": ")?;
+
+        let (winning, have) = parse::split_once(rest, " | ")?;
+
+        fn parse_numbers(s: &str) -> Result<HashSet<u8>, ParseError> {
+            s.trim()
+                .split_ascii_whitespace()
+                .map(|s| parse::value(s, "a number"))
+                .collect()
+        }
+
+        let winning_numbers = parse_numbers(winning)?;
+        let have_numbers = parse_numbers(have)?;
+
+        Ok(Card {
+            winning_numbers,
+            have_numbers,
+        })
+    }
+
+    fn matching_numbers(&self) -> usize {
+        self.winning_numbers
+            .intersection(&self.have_numbers)
+            .count()
+    }
+}
+
+fn card_matches(input: &str) -> Result<Vec<usize>, ParseError> {
+    input
+        .lines()
+        .map(|line| Ok(Card::new(line)?.matching_numbers()))
+        .collect()
+}
+
+fn pile_worth(card_matches: &[usize]) -> u32 {
+    card_matches
+        .iter()
+        .copied()
+        .map(|n| if n == 0 { 0 } else { 2_u32.pow(n as u32 - 1) })
+        .sum()
+}
+
+fn total_cards(card_matches: &[usize]) -> usize {
+    let mut card_copies: Vec<_> = card_matches.iter().map(|_| 1).collect();
+
+    // Copies won't be made past the end of the table, even if the table is
+    // cut short.
+    for (i, value) in card_matches.iter().enumerate() {
+        for cm in i + 1..(i + 1 + value).min(card_copies.len()) {
+            let amt = card_copies[i];
+            card_copies[cm] += amt;
+        }
+    }
+
+    card_copies.iter().sum()
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    let card_original_matches = card_matches(INPUT).unwrap();
+
+    // Part 1.
+    println!("Part 1");
+    let pile_worth = pile_worth(&card_original_matches);
+    println!("Pile worth: {pile_worth}");
+    assert_eq!(pile_worth, 13);
+
+    // Part 2.
+    println!("Part 2:");
+    let total_cards = total_cards(&card_original_matches);
+    println!("Total cards: {total_cards}");
+    assert_eq!(total_cards, 30);
+}
+
+#[test]
+fn cut_short() {
+    // Only the first three cards of the example, the first of which wins
+    // copies of four more.
+    assert_eq!(total_cards(&[4, 2, 2]), 7);
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        card_matches(input)
+    }
+
+    fn part1(card_matches: &Vec<usize>) -> String {
+        pile_worth(card_matches).to_string()
+    }
+
+    fn part2(card_matches: &Vec<usize>) -> String {
+        total_cards(card_matches).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day04);
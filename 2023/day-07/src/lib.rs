@@ -0,0 +1,258 @@
+use aoc_common::{parse, ParseError, Part, Solution};
+use itertools::Itertools;
+use std::cmp::Ordering;
+
+const JACK: char = 'J';
+
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug)]
+enum HandType {
+    HighCard = 1,
+    OnePair = 2,
+    TwoPair = 3,
+    ThreeOfAKind = 4,
+    FullHouse = 5,
+    FourOfAKind = 6,
+    FiveOfAKind = 7,
+}
+
+fn card_value(c: char, part: Part) -> u8 {
+    match c {
+        c @ '2'..='9' => c.to_digit(10).expect("digit") as u8,
+        'T' => 10,
+        JACK => match part {
+            Part::One => 11,
+            Part::Two => 1,
+        },
+        'Q' => 12,
+        'K' => 13,
+        'A' => 14,
+        c => panic!("unexpected card {c}"),
+    }
+}
+
+fn hand_type(hand: &str, part: Part) -> HandType {
+    let mut sorted = hand.chars().collect::<Vec<_>>();
+    sorted.sort_by_key(|c| card_value(*c, part));
+
+    let mut jacks_count = 0;
+    let mut i = 0;
+    let mut runs = vec![];
+    match part {
+        Part::One => {}
+        Part::Two => {
+            while i < sorted.len() && sorted[i] == JACK {
+                jacks_count += 1;
+                i += 1;
+            }
+        }
+    }
+
+    if i < sorted.len() {
+        runs.push((sorted[i], 1i8));
+        i += 1;
+        for c in &sorted[i..] {
+            match part {
+                Part::One => {}
+                Part::Two => {
+                    if *c == JACK {
+                        jacks_count += 1;
+                        continue;
+                    }
+                }
+            }
+            let last = runs.last_mut().expect("last");
+            if *c == last.0 {
+                last.1 += 1;
+            } else {
+                runs.push((*c, 1));
+            }
+        }
+    }
+
+    runs.sort_by_key(|&(_, count)| -count);
+
+    let most = if !runs.is_empty() {
+        runs[0].1
+    } else {
+        assert_eq!(jacks_count, hand.len());
+        0
+    };
+
+    match most + jacks_count as i8 {
+        5 => HandType::FiveOfAKind,
+        4 => HandType::FourOfAKind,
+        3 => {
+            if runs[1].1 == 2 {
+                HandType::FullHouse
+            } else {
+                HandType::ThreeOfAKind
+            }
+        }
+        2 => {
+            if runs[1].1 == 2 {
+                HandType::TwoPair
+            } else {
+                HandType::OnePair
+            }
+        }
+        1 => HandType::HighCard,
+        _ => panic!("card counting error"),
+    }
+}
+
+#[test]
+fn test_hand_type() {
+    assert_eq!(hand_type("32T3K", Part::One), HandType::OnePair,);
+    assert_eq!(hand_type("T55J5", Part::One), HandType::ThreeOfAKind,);
+    assert_eq!(hand_type("KK677", Part::One), HandType::TwoPair,);
+    assert_eq!(hand_type("KTJJT", Part::One), HandType::TwoPair,);
+    assert_eq!(hand_type("QQQJA", Part::One), HandType::ThreeOfAKind,);
+
+    assert_eq!(hand_type("32T3K", Part::Two), HandType::OnePair,);
+    assert_eq!(hand_type("T55J5", Part::Two), HandType::FourOfAKind,);
+    assert_eq!(hand_type("KK677", Part::Two), HandType::TwoPair,);
+    assert_eq!(hand_type("KKJ77", Part::Two), HandType::FullHouse,);
+    assert_eq!(hand_type("JKK77", Part::Two), HandType::FullHouse,);
+    assert_eq!(hand_type("KTJJT", Part::Two), HandType::FourOfAKind,);
+    assert_eq!(hand_type("QQQJA", Part::Two), HandType::FourOfAKind,);
+    assert_eq!(hand_type("J2345", Part::Two), HandType::OnePair,);
+    assert_eq!(hand_type("2J345", Part::Two), HandType::OnePair,);
+    assert_eq!(hand_type("23J45", Part::Two), HandType::OnePair,);
+    assert_eq!(hand_type("234J5", Part::Two), HandType::OnePair,);
+    assert_eq!(hand_type("2345J", Part::Two), HandType::OnePair,);
+
+    assert_eq!(hand_type("JJJJJ", Part::One), HandType::FiveOfAKind);
+    assert_eq!(hand_type("JJJJJ", Part::One), HandType::FiveOfAKind);
+    assert_eq!(hand_type("JJJJQ", Part::One), HandType::FourOfAKind);
+    assert_eq!(hand_type("JJJJQ", Part::Two), HandType::FiveOfAKind);
+    assert_eq!(hand_type("JJQJJ", Part::One), HandType::FourOfAKind);
+    assert_eq!(hand_type("JJQJJ", Part::Two), HandType::FiveOfAKind);
+}
+
+pub type HandAndBid<'a> = (&'a str, u32);
+
+fn hands_and_bids(s: &str) -> Result<Vec<HandAndBid<'_>>, ParseError> {
+    s.lines()
+        .map(|s| {
+            let (hand, bid) = parse::split_once(s, " ")?;
+            if hand.len() != 5 || !hand.chars().all(|c| "23456789TJQKA".contains(c)) {
+                return Err(ParseError::new(hand, "a hand of five cards"));
+            }
+            Ok((hand, parse::value(bid, "a bid")?))
+        })
+        .collect()
+}
+
+fn compare_by_type_then_cards_in_order(h1: &str, h2: &str, part: Part) -> Ordering {
+    match hand_type(h1, part).cmp(&hand_type(h2, part)) {
+        Ordering::Equal => {
+            for (c1, c2) in Itertools::zip_eq(h1.chars(), h2.chars()) {
+                match card_value(c1, part).cmp(&card_value(c2, part)) {
+                    Ordering::Equal => continue,
+                    ordering => return ordering,
+                }
+            }
+
+            panic!("unexpected equal hands?");
+        }
+        ordering => ordering,
+    }
+}
+
+#[test]
+fn comparisons() {
+    macro_rules! compare {
+        ($hand1:literal, $hand2:literal, $part:path, $expect:path) => {
+            assert_eq!(
+                compare_by_type_then_cards_in_order($hand1, $hand2, $part),
+                $expect
+            );
+
+            assert_eq!(
+                compare_by_type_then_cards_in_order($hand2, $hand1, $part),
+                $expect.reverse()
+            )
+        };
+    }
+
+    compare!("2345J", "23455", Part::One, Ordering::Less);
+    compare!("2345J", "23455", Part::Two, Ordering::Less);
+
+    compare!("2345J", "23454", Part::One, Ordering::Less);
+    compare!("2345J", "23454", Part::Two, Ordering::Less);
+
+    compare!("23329", "2332J", Part::One, Ordering::Less);
+    compare!("23329", "2332J", Part::Two, Ordering::Less);
+
+    compare!("JKKK2", "QQQQ2", Part::One, Ordering::Less);
+    compare!("JKKK2", "QQQQ2", Part::Two, Ordering::Less);
+
+    compare!("KJKK2", "QQQQ2", Part::One, Ordering::Less);
+    compare!("KJKK2", "QQQQ2", Part::Two, Ordering::Greater);
+
+    compare!("22345", "23455", Part::One, Ordering::Less);
+    compare!("22325", "23455", Part::One, Ordering::Greater);
+    compare!("22345", "23455", Part::One, Ordering::Less);
+    compare!("22345", "23455", Part::One, Ordering::Less);
+
+    compare!("2JJ34", "29934", Part::One, Ordering::Greater);
+    compare!("JJ324", "99324", Part::One, Ordering::Greater);
+}
+
+fn total_winnings(hands_bids: &[HandAndBid<'_>], part: Part) -> usize {
+    let mut sorted_hands = hands_bids.to_vec();
+    sorted_hands.sort_by(|(h1, _), (h2, _)| compare_by_type_then_cards_in_order(h1, h2, part));
+
+    let mut total_winnings = 0;
+    for (i, (_, bid)) in sorted_hands.iter().enumerate() {
+        let i = i + 1;
+        total_winnings += *bid as usize * i;
+    }
+    total_winnings
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    let original_hands_bids = hands_and_bids(INPUT).unwrap();
+
+    // Part 1.
+    println!("Part 1:");
+    let winnings = total_winnings(&original_hands_bids, Part::One);
+    println!("Total winnings: {winnings}");
+    assert_eq!(winnings, 6440);
+
+    // Part 2.
+    println!("Part 2:");
+    let winnings = total_winnings(&original_hands_bids, Part::Two);
+    println!("Total winnings: {winnings}");
+    assert_eq!(winnings, 5905);
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 7;
+
+    type Parsed<'a> = Vec<HandAndBid<'a>>;
+
+    fn parse(input: &str) -> Result<Vec<HandAndBid<'_>>, ParseError> {
+        hands_and_bids(input)
+    }
+
+    fn part1(hands_bids: &Vec<HandAndBid<'_>>) -> String {
+        total_winnings(hands_bids, Part::One).to_string()
+    }
+
+    fn part2(hands_bids: &Vec<HandAndBid<'_>>) -> String {
+        total_winnings(hands_bids, Part::Two).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day07);
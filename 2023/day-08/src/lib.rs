@@ -0,0 +1,392 @@
+use aoc_common::cycle::{self, Cycle};
+use aoc_common::{parse, ParseError, Solution};
+use num::integer::{ExtendedGcd, Integer};
+use std::collections::HashMap;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum Direction {
+    Left,
+    Right,
+}
+
+pub struct Challenge<'a> {
+    directions: Vec<Direction>,
+    start_nodes: Vec<&'a str>,
+    network: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
+static AAA: &str = "AAA";
+static ZZZ: &str = "ZZZ";
+
+impl<'a> Challenge<'a> {
+    fn new(s: &'a str) -> Result<Challenge<'a>, ParseError> {
+        let mut lines = s.lines();
+
+        let directions = parse::next(&mut lines, s, "directions")?;
+        let directions = directions
+            .char_indices()
+            .map(|(i, c)| match c {
+                'L' => Ok(Direction::Left),
+                'R' => Ok(Direction::Right),
+                c => Err(ParseError::new(&directions[i..i + c.len_utf8()], "L or R")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let empty = parse::next(&mut lines, s, "an empty line")?;
+        if !empty.is_empty() {
+            return Err(ParseError::new(empty, "an empty line"));
+        }
+
+        let mut network = HashMap::new();
+        let mut nexts_in_order = vec![];
+        let mut start_nodes = vec![];
+        for line in lines {
+            // AAA = (BBB, CCC)
+            let (node, nexts) = parse::split_once(line, " = ")?;
+            let nexts = parse::strip_suffix(parse::strip_prefix(nexts, "(")?, ")")?;
+            let nexts = parse::split_once(nexts, ", ")?;
+
+            if node.ends_with('A') {
+                start_nodes.push(node);
+            }
+
+            network.insert(node, nexts);
+            nexts_in_order.push(nexts);
+        }
+
+        // Every node leads to nodes in the network, and if there's a node
+        // AAA to walk from, there's a node ZZZ to walk to.
+        for (left, right) in nexts_in_order {
+            for next in [left, right] {
+                if !network.contains_key(next) {
+                    return Err(ParseError::new(next, "a node in the network"));
+                }
+            }
+        }
+        if network.contains_key(AAA) && !network.contains_key(ZZZ) {
+            return Err(ParseError::after(s, "a node ZZZ"));
+        }
+
+        Ok(Challenge {
+            directions,
+            start_nodes,
+            network,
+        })
+    }
+}
+
+fn distance_start_to_end(challenge: &Challenge<'_>) -> usize {
+    let mut distance = 0;
+    let mut current_node = AAA;
+
+    let mut directions = challenge.directions.iter().copied().cycle();
+
+    let network = &challenge.network;
+    loop {
+        if current_node == ZZZ {
+            break;
+        }
+
+        distance += 1;
+
+        let (left, right) = network.get(&current_node).expect("current_node");
+
+        match directions.next().expect("direction") {
+            Direction::Left => {
+                current_node = left;
+            }
+            Direction::Right => {
+                current_node = right;
+            }
+        }
+    }
+
+    distance
+}
+
+impl<'a> Challenge<'a> {
+    /// Where one step from `node`, taking the direction at `direction`
+    /// in the list of directions, leads, and the direction to take next.
+    fn step(&self, (node, direction): (&'a str, usize)) -> (&'a str, usize) {
+        let (left, right) = self.network.get(node).expect("node in network");
+        let next = match self.directions[direction] {
+            Direction::Left => left,
+            Direction::Right => right,
+        };
+        (next, (direction + 1) % self.directions.len())
+    }
+}
+
+/// The steps at which one walker, from its start node, is on a node ending
+/// in Z: those before its walk runs into a cycle, and those within the
+/// cycle, which recur every time around it.
+struct EndSteps {
+    cycle: Cycle,
+    before_cycle: Vec<u64>,
+    in_cycle: Vec<u64>,
+}
+
+impl EndSteps {
+    fn new(challenge: &Challenge<'_>, start_node: &str) -> EndSteps {
+        let start = (start_node, 0);
+        let cycle = cycle::brent(start, |&state| challenge.step(state));
+
+        let mut before_cycle = vec![];
+        let mut in_cycle = vec![];
+        let mut state = (start_node, 0);
+        for step in 0..cycle.start + cycle.length {
+            if state.0.ends_with('Z') {
+                if step < cycle.start {
+                    before_cycle.push(step as u64);
+                } else {
+                    in_cycle.push(step as u64);
+                }
+            }
+            state = challenge.step(state);
+        }
+
+        EndSteps {
+            cycle,
+            before_cycle,
+            in_cycle,
+        }
+    }
+
+    fn is_end(&self, step: u64) -> bool {
+        let (step, _) = self.cycle.reduce(step);
+        let step = step as u64;
+        self.before_cycle.contains(&step) || self.in_cycle.contains(&step)
+    }
+}
+
+/// The least `x` with `x == r1 (mod m1)` and `x == r2 (mod m2)`, modulo the
+/// moduli's least common multiple, if there is one.
+fn chinese_remainder((r1, m1): (i128, i128), (r2, m2): (i128, i128)) -> Option<(i128, i128)> {
+    // m1 * x + m2 * y == gcd
+    let ExtendedGcd { gcd, x, .. } = m1.extended_gcd(&m2);
+    if (r2 - r1) % gcd != 0 {
+        return None;
+    }
+
+    let lcm = m1 / gcd * m2;
+    let k = ((r2 - r1) / gcd * x).rem_euclid(m2 / gcd);
+    Some(((r1 + m1 * k).rem_euclid(lcm), lcm))
+}
+
+/// How many steps until every walker, each starting on a different node
+/// ending in A, is on a node ending in Z at the same time.
+fn distance_start_to_simultaneous_end(challenge: &Challenge<'_>) -> u64 {
+    let walkers = challenge
+        .start_nodes
+        .iter()
+        .map(|start_node| EndSteps::new(challenge, start_node))
+        .collect::<Vec<_>>();
+
+    // Until every walker is in its cycle, just try every step.
+    let all_in_cycles = walkers
+        .iter()
+        .map(|walker| walker.cycle.start as u64)
+        .max()
+        .unwrap_or(0);
+    if let Some(step) = (1..all_in_cycles).find(|&step| walkers.iter().all(|w| w.is_end(step))) {
+        return step;
+    }
+
+    // After that, every walker is on a Z node at steps congruent to one of
+    // its cycle's Z steps, modulo its cycle length.  Try every combination
+    // of those.
+    let mut candidates = vec![(0i128, 1i128)];
+    for walker in &walkers {
+        let length = walker.cycle.length as i128;
+        candidates = candidates
+            .iter()
+            .flat_map(|&candidate| {
+                walker.in_cycle.iter().filter_map(move |&step| {
+                    chinese_remainder(candidate, (step as i128 % length, length))
+                })
+            })
+            .collect();
+    }
+
+    candidates
+        .into_iter()
+        .map(|(step, modulus)| {
+            // The first such step once they're all in their cycles.
+            let all_in_cycles = i128::from(all_in_cycles.max(1));
+            let behind = (all_in_cycles - step).max(0);
+            step + (behind + modulus - 1) / modulus * modulus
+        })
+        .min()
+        .map(|step| u64::try_from(step).expect("steps fit in u64"))
+        .expect("walkers never simultaneously on nodes ending in Z")
+}
+
+#[test]
+fn my_example() {
+    // Part 2.
+    static INPUT3: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    let challenge3 = Challenge::new(INPUT3).unwrap();
+    let dist3 = distance_start_to_simultaneous_end(&challenge3);
+    println!("Distance 3: {dist3}");
+    assert_eq!(dist3, 6);
+}
+
+#[test]
+fn example() {
+    // Part 1.
+    static INPUT1: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    let challenge1 = Challenge::new(INPUT1).unwrap();
+
+    println!("Part 1:");
+    let dist1 = distance_start_to_end(&challenge1);
+    println!("Distance 1: {dist1}");
+    assert_eq!(dist1, 2);
+
+    static INPUT2: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    let challenge2 = Challenge::new(INPUT2).unwrap();
+    let dist2 = distance_start_to_end(&challenge2);
+    println!("Distance 2: {dist2}");
+    assert_eq!(dist2, 6);
+
+    // Part 2.
+    static INPUT3: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    let challenge3 = Challenge::new(INPUT3).unwrap();
+    let dist3 = distance_start_to_simultaneous_end(&challenge3);
+    println!("Distance 3: {dist3}");
+    assert_eq!(dist3, 6);
+}
+
+#[test]
+fn uneven_cycles() {
+    // Walk every walker along together until they're all on Z nodes.
+    fn walk_together(challenge: &Challenge<'_>) -> u64 {
+        let mut states = challenge
+            .start_nodes
+            .iter()
+            .map(|&node| (node, 0))
+            .collect::<Vec<_>>();
+        let mut steps = 0;
+        loop {
+            states = states.iter().map(|&state| challenge.step(state)).collect();
+            steps += 1;
+            if states.iter().all(|(node, _)| node.ends_with('Z')) {
+                return steps;
+            }
+        }
+    }
+
+    // Walkers that reach Z nodes before their walks start cycling, and
+    // several times, unevenly spaced, each time around their cycles.
+    static INPUT: &str = "RLR
+
+11A = (11Z, 11H)
+11Y = (11F, 11H)
+11F = (11Z, 11B)
+11H = (11B, 11B)
+11B = (11H, 11Z)
+11Z = (11B, 11F)
+22A = (22D, 22Y)
+22H = (22H, 22Z)
+22D = (22Z, 22H)
+22G = (22D, 22G)
+22Y = (22Z, 22G)
+22Z = (22H, 22G)
+33A = (33G, 33D)
+33H = (33C, 33H)
+33C = (33C, 33Z)
+33G = (33Z, 33H)
+33D = (33G, 33Z)
+33Z = (33C, 33C)";
+
+    let challenge = Challenge::new(INPUT).unwrap();
+    assert_eq!(
+        distance_start_to_simultaneous_end(&challenge),
+        walk_together(&challenge)
+    );
+    assert_eq!(distance_start_to_simultaneous_end(&challenge), 21);
+}
+
+#[test]
+fn incomplete_networks() {
+    let error = |input| {
+        Challenge::new(input)
+            .err()
+            .unwrap()
+            .locate(input)
+            .to_string()
+    };
+    assert_eq!(
+        error("L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)"),
+        "line 3, column 8: expected a node in the network, found \"BBB\""
+    );
+    assert_eq!(
+        error("L\n\nAAA = (AAA, AAA)"),
+        "line 3, column 17: expected a node ZZZ, found \"\""
+    );
+
+    // Without AAA, there's nothing to walk for part 1, but there may be
+    // for part 2.
+    let challenge = Challenge::new("L\n\n11A = (11Z, 11Z)\n11Z = (11Z, 11Z)").unwrap();
+    assert_eq!(Day08::part1(&challenge), "no node AAA to start from");
+    assert_eq!(Day08::part2(&challenge), "1");
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
+
+    type Parsed<'a> = Challenge<'a>;
+
+    fn parse(input: &str) -> Result<Challenge<'_>, ParseError> {
+        Challenge::new(input)
+    }
+
+    fn part1(challenge: &Challenge<'_>) -> String {
+        if !challenge.network.contains_key(AAA) {
+            return "no node AAA to start from".to_string();
+        }
+        distance_start_to_end(challenge).to_string()
+    }
+
+    fn part2(challenge: &Challenge<'_>) -> String {
+        distance_start_to_simultaneous_end(challenge).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day08);
@@ -0,0 +1,98 @@
+use aoc_common::{parse, ParseError, Solution};
+use itertools::Itertools;
+
+fn find_next_value(nums: &[i32]) -> i32 {
+    let mut all_zeroes = true;
+    let diffs = Itertools::tuple_windows(nums.iter())
+        .map(|(x, y)| {
+            let diff = y - x;
+            all_zeroes = all_zeroes && diff == 0;
+            diff
+        })
+        .collect::<Vec<_>>();
+
+    let end = *nums.last().expect("last");
+    if all_zeroes {
+        end
+    } else {
+        end + find_next_value(&diffs)
+    }
+}
+
+#[test]
+fn next_value_test() {
+    assert_eq!(find_next_value(&[0, 0, 0, 0]), 0);
+    assert_eq!(find_next_value(&[3, 3, 3, 3]), 3);
+    assert_eq!(find_next_value(&[0, 3, 6, 9]), 12);
+    assert_eq!(find_next_value(&[1, 3, 6, 10, 15, 21]), 28);
+}
+
+fn parse_values(line: &str) -> Result<Vec<i32>, ParseError> {
+    line.split_ascii_whitespace()
+        .map(|n| parse::value(n, "a number"))
+        .collect()
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input.lines().map(parse_values).collect()
+}
+
+fn sum_next_values(values: &[Vec<i32>]) -> i32 {
+    values.iter().map(|nums| find_next_value(nums)).sum()
+}
+
+fn sum_previous_values(values: &[Vec<i32>]) -> i32 {
+    let reversed: Vec<Vec<i32>> = values
+        .iter()
+        .cloned()
+        .map(|mut v| {
+            v.reverse();
+            v
+        })
+        .collect();
+    sum_next_values(&reversed)
+}
+
+#[test]
+fn example() {
+    static INPUT: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    let parsed = parse_input(INPUT).unwrap();
+
+    // Part 1.
+    println!("Part 1:");
+    let sum_nexts = sum_next_values(&parsed);
+    println!("Sum: {sum_nexts}");
+    assert_eq!(sum_nexts, 114);
+
+    // Part 2.
+    println!("Part 2:");
+    let sum_prevs = sum_previous_values(&parsed);
+    println!("Sum (backward): {sum_prevs}");
+    assert_eq!(sum_prevs, 2);
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 9;
+
+    type Parsed<'a> = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(values: &Vec<Vec<i32>>) -> String {
+        sum_next_values(values).to_string()
+    }
+
+    fn part2(values: &Vec<Vec<i32>>) -> String {
+        sum_previous_values(values).to_string()
+    }
+}
+
+aoc_common::example_tests!(Day09);
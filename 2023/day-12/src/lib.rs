@@ -0,0 +1,183 @@
+use aoc_common::{parse, ParseError, Solution};
+use itertools::Itertools;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Spring {
+    Working,
+    Damaged,
+    Unknown,
+}
+
+pub struct ConditionRecord {
+    springs: Vec<Spring>,
+    damaged_run_lengths: Vec<usize>,
+}
+
+impl ConditionRecord {
+    fn new(s: &str) -> Result<ConditionRecord, ParseError> {
+        let (springs, damaged_run_lengths) = parse::split_once(s, " ")?;
+
+        let springs = springs
+            .char_indices()
+            .map(|(i, c)| match c {
+                '.' => Ok(Spring::Working),
+                '#' => Ok(Spring::Damaged),
+                '?' => Ok(Spring::Unknown),
+                c => Err(ParseError::new(
+                    &springs[i..i + c.len_utf8()],
+                    "'.', '#', or '?'",
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let damaged_run_lengths = damaged_run_lengths
+            .split(',')
+            .map(|n| parse::value(n, "a run length"))
+            .collect::<Result<_, _>>()?;
+
+        Ok(ConditionRecord {
+            springs,
+            damaged_run_lengths,
+        })
+    }
+
+    fn count_valid_arrangements(&self) -> u64 {
+        let total_spring_count = self.springs.len();
+
+        let mut prev_counts = vec![0u64; total_spring_count + 2];
+        prev_counts[total_spring_count + 1] = 1;
+
+        // Consider each damaged-length from last to first.
+        for damaged_len in self.damaged_run_lengths.iter().copied().rev() {
+            let mut possibly_damaged_run_len = 0;
+
+            let mut nways = 0;
+
+            let mut curr_counts = vec![0u64; total_spring_count + 2];
+
+            // Attempt to place a damaged-length at each possible location from end of spring sequence to start.
+            for s in (0..total_spring_count).rev() {
+                nways = if let Some(Spring::Damaged) = self.springs.get(s + damaged_len) {
+                    0
+                } else {
+                    match prev_counts.get(s + damaged_len + 1) {
+                        Some(ways) => nways + *ways,
+                        None => 0,
+                    }
+                };
+
+                curr_counts[s] = match self.springs[s] {
+                    Spring::Working => {
+                        possibly_damaged_run_len = 0;
+                        0
+                    }
+                    Spring::Unknown | Spring::Damaged => {
+                        possibly_damaged_run_len += 1;
+
+                        if possibly_damaged_run_len >= damaged_len
+                            && (s == 0 || self.springs[s - 1] != Spring::Damaged)
+                            && {
+                                let limit = s + damaged_len;
+                                limit == total_spring_count
+                                    || self.springs[limit] != Spring::Damaged
+                            }
+                        {
+                            nways
+                        } else {
+                            0
+                        }
+                    }
+                };
+            }
+
+            prev_counts = curr_counts;
+        }
+
+        if false {
+            println!("final counts:");
+            println!("{prev_counts:?}");
+        }
+
+        let ans = Itertools::take_while_inclusive(
+            prev_counts.iter().take(total_spring_count).enumerate(),
+            |(s, _)| self.springs[*s] != Spring::Damaged,
+        )
+        .map(|(_s, ways)| *ways)
+        .sum();
+
+        if false {
+            println!("Computed answer: {ans}");
+        }
+
+        ans
+    }
+}
+
+mod tests;
+
+pub fn parse_input(input: &str) -> Result<Vec<ConditionRecord>, ParseError> {
+    input.trim().lines().map(ConditionRecord::new).collect()
+}
+
+/// Unfold each record into the five copies it's really made of.
+pub fn repeat_records(records: &[ConditionRecord]) -> impl Iterator<Item = ConditionRecord> + '_ {
+    records.iter().map(
+        |ConditionRecord {
+             springs: ref single_springs,
+             damaged_run_lengths: ref single_damaged_run_lengths,
+         }| {
+            const N: usize = 5;
+
+            let springs = {
+                let mut springs = single_springs.clone();
+                for _ in 0..N - 1 {
+                    springs.push(Spring::Unknown);
+                    springs.extend_from_slice(single_springs);
+                }
+                springs
+            };
+
+            let damaged_run_lengths = {
+                let mut damaged_run_lengths = vec![];
+                for _ in 0..N {
+                    damaged_run_lengths.extend_from_slice(single_damaged_run_lengths);
+                }
+                damaged_run_lengths
+            };
+
+            ConditionRecord {
+                springs,
+                damaged_run_lengths,
+            }
+        },
+    )
+}
+
+fn sum_valid_arrangements(records: impl Iterator<Item = ConditionRecord>) -> u64 {
+    records.map(|rec| rec.count_valid_arrangements()).sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 12;
+
+    type Parsed<'a> = Vec<ConditionRecord>;
+
+    fn parse(input: &str) -> Result<Vec<ConditionRecord>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(records: &Vec<ConditionRecord>) -> String {
+        records
+            .iter()
+            .map(|rec| rec.count_valid_arrangements())
+            .sum::<u64>()
+            .to_string()
+    }
+
+    fn part2(records: &Vec<ConditionRecord>) -> String {
+        sum_valid_arrangements(repeat_records(records)).to_string()
+    }
+}
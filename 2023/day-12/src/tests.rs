@@ -1,10 +1,10 @@
 #![cfg(test)]
 
-use crate::{parse_input, parse_input_repeated, ConditionRecord};
+use crate::{parse_input, repeat_records, ConditionRecord};
 
 fn line_to_valid_arrangements(line: &str) -> u64 {
     println!("-------------------------\nConsidering: {line}");
-    let cr = ConditionRecord::new(line);
+    let cr = ConditionRecord::new(line).unwrap();
     cr.count_valid_arrangements()
 }
 
@@ -142,9 +142,12 @@ fn example() {
 ?###???????? 3,2,1
     ";
 
+    let records = parse_input(INPUT).unwrap();
+
     // Part 1.
     println!("Part 1");
-    let sum: u64 = parse_input(INPUT)
+    let sum: u64 = records
+        .iter()
         .map(|rec| rec.count_valid_arrangements())
         .sum();
     println!("Sum: {sum}");
@@ -152,9 +155,20 @@ fn example() {
 
     // Part 2.
     println!("Part 2");
-    let sum: u64 = parse_input_repeated(INPUT)
+    let sum: u64 = repeat_records(&records)
         .map(|rec| rec.count_valid_arrangements())
         .sum();
     println!("Sum: {sum}");
     assert_eq!(sum, 525152);
 }
+
+#[test]
+fn bad_record() {
+    let input = "???.### 1,1,3\n.??..??...?##. 1,x,3\n";
+
+    let error = parse_input(input).err().unwrap().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 18: expected a run length, found \"x\""
+    );
+}
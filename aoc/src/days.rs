@@ -0,0 +1,61 @@
+//! Every day's solution known to the runner.
+
+use aoc_common::Solution;
+
+use crate::Day;
+
+macro_rules! days {
+    ($($krate:ident::$day:ident),* $(,)?) => {
+        /// All known days, ordered by year and then by day.
+        pub static DAYS: &[Day] = &[
+            $(Day {
+                year: <$krate::$day as Solution>::YEAR,
+                day: <$krate::$day as Solution>::DAY,
+                run: aoc_common::run::<$krate::$day>,
+                bench: aoc_common::bench::<$krate::$day>,
+            },)*
+        ];
+    };
+}
+
+days! {
+    aoc_2021_day_01::Day01,
+    aoc_2021_day_02::Day02,
+    aoc_2022_day_01::Day01,
+    aoc_2022_day_02::Day02,
+    aoc_2022_day_03::Day03,
+    aoc_2022_day_04::Day04,
+    aoc_2022_day_05::Day05,
+    aoc_2022_day_06::Day06,
+    aoc_2022_day_07::Day07,
+    aoc_2022_day_08::Day08,
+    aoc_2022_day_09::Day09,
+    aoc_2022_day_10::Day10,
+    aoc_2022_day_11::Day11,
+    aoc_2022_day_12::Day12,
+    aoc_2022_day_13::Day13,
+    aoc_2022_day_14::Day14,
+    aoc_2022_day_15::Day15,
+    aoc_2022_day_16::Day16,
+    aoc_2022_day_17::Day17,
+    aoc_2022_day_18::Day18,
+    aoc_2022_day_19::Day19,
+    aoc_2022_day_20::Day20,
+    aoc_2022_day_21::Day21,
+    aoc_2022_day_22::Day22,
+    aoc_2022_day_23::Day23,
+    aoc_2022_day_24::Day24,
+    aoc_2022_day_25::Day25,
+    aoc_2023_day_01::Day01,
+    aoc_2023_day_02::Day02,
+    aoc_2023_day_03::Day03,
+    aoc_2023_day_04::Day04,
+    aoc_2023_day_05::Day05,
+    aoc_2023_day_06::Day06,
+    aoc_2023_day_07::Day07,
+    aoc_2023_day_08::Day08,
+    aoc_2023_day_09::Day09,
+    aoc_2023_day_10::Day10,
+    aoc_2023_day_11::Day11,
+    aoc_2023_day_12::Day12,
+}
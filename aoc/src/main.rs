@@ -0,0 +1,442 @@
+//! Run any day's solution from one binary, e.g. `aoc run 2022 16 --part 2`.
+//!
+//! Each day's input is read from the file given by `--input` (`-` for
+//! standard input), or else from the input cache directory named by
+//! `AOC_INPUT_DIR`.  With `--verify`, answers are checked against those
+//! recorded in each day's `answers.toml` there.
+//!
+//! `aoc bench` times parsing and each part instead, appends the timings to a
+//! JSON history (`bench-history.json` in the input cache directory unless
+//! `--history` says otherwise), and fails if any timing is slower than its
+//! previous one by more than `--threshold` percent.
+
+mod days;
+
+use aoc_common::{BenchRun, History, InputSource, KnownAnswers, ParseError, Part, Tally, Timing};
+use days::DAYS;
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::SystemTime;
+
+/// Run the given parts of a day's solution on its input, checking answers
+/// against those known.
+type Run = fn(&str, &[Part], Option<&KnownAnswers>) -> Result<Tally, ParseError>;
+
+/// Time the given parts of a day's solution on its input, over some number
+/// of runs.
+type Bench = fn(&str, &[Part], u32) -> Result<Vec<Timing>, ParseError>;
+
+/// One day's solution, with its type erased so that every day fits in one
+/// table.
+pub struct Day {
+    year: u16,
+    day: u8,
+    run: Run,
+    bench: Bench,
+}
+
+const USAGE: &str = "usage: aoc run <year> [<day>] [--part <1|2>] [--input <file|->] [--verify]
+       aoc bench <year> [<day>] [--part <1|2>] [--input <file|->] [--runs <n>]
+                 [--threshold <percent>] [--history <file>]
+       aoc list";
+
+/// How many times each day is run when benchmarking, by default.
+const DEFAULT_RUNS: u32 = 10;
+
+/// How much slower than before, in percent, a timing must be to count as a
+/// regression, by default.
+const DEFAULT_THRESHOLD: u32 = 10;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Run {
+        year: u16,
+        day: Option<u8>,
+        part: Option<Part>,
+        input: Option<String>,
+        verify: bool,
+    },
+    Bench {
+        year: u16,
+        day: Option<u8>,
+        part: Option<Part>,
+        input: Option<String>,
+        runs: u32,
+        threshold: u32,
+        history: Option<String>,
+    },
+    List,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let command = args.next().ok_or("no command given")?;
+    match command.as_str() {
+        "run" | "bench" => {
+            let benching = command == "bench";
+
+            let mut year = None;
+            let mut day = None;
+            let mut part = None;
+            let mut input = None;
+            let mut verify = false;
+            let mut runs = DEFAULT_RUNS;
+            let mut threshold = DEFAULT_THRESHOLD;
+            let mut history = None;
+
+            while let Some(arg) = args.next() {
+                if arg == "--part" {
+                    let n = args.next().ok_or("--part requires a part number")?;
+                    part = Some(match n.as_str() {
+                        "1" => Part::One,
+                        "2" => Part::Two,
+                        _ => return Err(format!("bad part number: {n:?}")),
+                    });
+                } else if arg == "--input" {
+                    input = Some(
+                        args.next()
+                            .ok_or("--input requires a file, or - for stdin")?,
+                    );
+                } else if arg == "--verify" && !benching {
+                    verify = true;
+                } else if arg == "--runs" && benching {
+                    let n = args.next().ok_or("--runs requires a number")?;
+                    runs = match n.parse::<u32>() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(format!("bad number of runs: {n:?}")),
+                    };
+                } else if arg == "--threshold" && benching {
+                    let n = args.next().ok_or("--threshold requires a percentage")?;
+                    threshold = n
+                        .parse::<u32>()
+                        .map_err(|_| format!("bad threshold: {n:?}"))?;
+                } else if arg == "--history" && benching {
+                    history = Some(args.next().ok_or("--history requires a file")?);
+                } else if year.is_none() {
+                    year = Some(
+                        arg.parse::<u16>()
+                            .map_err(|_| format!("bad year: {arg:?}"))?,
+                    );
+                } else if day.is_none() {
+                    day = Some(arg.parse::<u8>().map_err(|_| format!("bad day: {arg:?}"))?);
+                } else {
+                    return Err(format!("unexpected argument: {arg:?}"));
+                }
+            }
+
+            let year = year.ok_or("no year given")?;
+            if input.is_some() && day.is_none() {
+                return Err("--input requires a day".into());
+            }
+            if benching {
+                Ok(Command::Bench {
+                    year,
+                    day,
+                    part,
+                    input,
+                    runs,
+                    threshold,
+                    history,
+                })
+            } else {
+                Ok(Command::Run {
+                    year,
+                    day,
+                    part,
+                    input,
+                    verify,
+                })
+            }
+        }
+        "list" => match args.next() {
+            Some(arg) => Err(format!("unexpected argument: {arg:?}")),
+            None => Ok(Command::List),
+        },
+        _ => Err(format!("unknown command: {command:?}")),
+    }
+}
+
+#[test]
+fn args() {
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    assert_eq!(
+        parse(&["run", "2022", "16", "--part", "2"]),
+        Ok(Command::Run {
+            year: 2022,
+            day: Some(16),
+            part: Some(Part::Two),
+            input: None,
+            verify: false,
+        })
+    );
+    assert_eq!(
+        parse(&["run", "--part", "1", "2023"]),
+        Ok(Command::Run {
+            year: 2023,
+            day: None,
+            part: Some(Part::One),
+            input: None,
+            verify: false,
+        })
+    );
+    assert_eq!(
+        parse(&["run", "2021"]),
+        Ok(Command::Run {
+            year: 2021,
+            day: None,
+            part: None,
+            input: None,
+            verify: false,
+        })
+    );
+    assert_eq!(
+        parse(&["run", "2022", "16", "--input", "-"]),
+        Ok(Command::Run {
+            year: 2022,
+            day: Some(16),
+            part: None,
+            input: Some("-".into()),
+            verify: false,
+        })
+    );
+    assert_eq!(
+        parse(&["run", "2022", "--verify", "--part", "1"]),
+        Ok(Command::Run {
+            year: 2022,
+            day: None,
+            part: Some(Part::One),
+            input: None,
+            verify: true,
+        })
+    );
+    assert_eq!(
+        parse(&["bench", "2022", "16", "--runs", "3", "--threshold", "25"]),
+        Ok(Command::Bench {
+            year: 2022,
+            day: Some(16),
+            part: None,
+            input: None,
+            runs: 3,
+            threshold: 25,
+            history: None,
+        })
+    );
+    assert_eq!(
+        parse(&["bench", "2023", "--history", "h.json", "--part", "2"]),
+        Ok(Command::Bench {
+            year: 2023,
+            day: None,
+            part: Some(Part::Two),
+            input: None,
+            runs: DEFAULT_RUNS,
+            threshold: DEFAULT_THRESHOLD,
+            history: Some("h.json".into()),
+        })
+    );
+    assert_eq!(parse(&["list"]), Ok(Command::List));
+
+    assert!(parse(&[]).is_err());
+    assert!(parse(&["run"]).is_err());
+    assert!(parse(&["run", "2022", "16", "--part", "3"]).is_err());
+    assert!(parse(&["run", "2022", "16", "17"]).is_err());
+    assert!(parse(&["run", "twenty"]).is_err());
+    assert!(parse(&["run", "2022", "--input", "input"]).is_err());
+    assert!(parse(&["run", "2022", "16", "--input"]).is_err());
+    assert!(parse(&["walk", "2022"]).is_err());
+    assert!(parse(&["run", "2022", "--runs", "3"]).is_err());
+    assert!(parse(&["bench", "2022", "--verify"]).is_err());
+    assert!(parse(&["bench", "2022", "--runs", "0"]).is_err());
+    assert!(parse(&["bench", "2022", "--threshold", "-5"]).is_err());
+}
+
+/// The days of `year` to run: all of them, or just `day`.  Reports that there
+/// are none if so.
+fn select_days(year: u16, day: Option<u8>) -> Option<Vec<&'static Day>> {
+    let days: Vec<_> = DAYS
+        .iter()
+        .filter(|d| d.year == year && day.is_none_or(|day| d.day == day))
+        .collect();
+
+    if days.is_empty() {
+        match day {
+            Some(day) => eprintln!("aoc: no solution for {year} day {day}"),
+            None => eprintln!("aoc: no solutions for {year}"),
+        }
+        return None;
+    }
+    Some(days)
+}
+
+fn parts(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+fn run(
+    year: u16,
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<String>,
+    verify: bool,
+) -> ExitCode {
+    let Some(days) = select_days(year, day) else {
+        return ExitCode::FAILURE;
+    };
+    let parts = parts(part);
+    let source = InputSource::from_arg(input.as_deref());
+
+    let mut failed = false;
+    let mut tally = Tally::default();
+    for d in days {
+        let input = match aoc_common::load_input(d.year, d.day, &source) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("aoc: {error}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let known = if verify {
+            match aoc_common::load_answers(d.year, d.day, &input) {
+                Ok(known) => Some(known),
+                Err(error) => {
+                    eprintln!("aoc: {error}");
+                    failed = true;
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        match (d.run)(&input, &parts, known.as_ref()) {
+            Ok(day_tally) => tally.add(day_tally),
+            Err(error) => {
+                eprintln!("aoc: {} day {}: bad input: {error}", d.year, d.day);
+                failed = true;
+            }
+        }
+    }
+
+    if verify {
+        println!("{tally}");
+    }
+    if failed || tally.failed > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn bench(
+    year: u16,
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<String>,
+    runs: u32,
+    threshold: u32,
+    history: Option<String>,
+) -> ExitCode {
+    let Some(days) = select_days(year, day) else {
+        return ExitCode::FAILURE;
+    };
+    let parts = parts(part);
+    let source = InputSource::from_arg(input.as_deref());
+
+    let history_path = history.map_or_else(aoc_common::history_path, PathBuf::from);
+    let mut history = match History::load(&history_path) {
+        Ok(history) => history,
+        Err(error) => {
+            eprintln!("aoc: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = false;
+    let mut run = BenchRun {
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+        timings: vec![],
+    };
+    for d in days {
+        let input = match aoc_common::load_input(d.year, d.day, &source) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("aoc: {error}");
+                failed = true;
+                continue;
+            }
+        };
+
+        match (d.bench)(&input, &parts, runs) {
+            Ok(timings) => {
+                for timing in &timings {
+                    println!("{timing}");
+                }
+                run.timings.extend(timings);
+            }
+            Err(error) => {
+                eprintln!("aoc: {} day {}: bad input: {error}", d.year, d.day);
+                failed = true;
+            }
+        }
+    }
+
+    let regressions = history.regressions(&run, threshold);
+    for regression in &regressions {
+        println!("REGRESSION {regression}");
+    }
+
+    history.runs.push(run);
+    if let Err(error) = history.save(&history_path) {
+        eprintln!("aoc: {error}");
+        failed = true;
+    }
+
+    if failed || !regressions.is_empty() {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(msg) => {
+            eprintln!("aoc: {msg}");
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            verify,
+        } => run(year, day, part, input, verify),
+        Command::Bench {
+            year,
+            day,
+            part,
+            input,
+            runs,
+            threshold,
+            history,
+        } => bench(year, day, part, input, runs, threshold, history),
+        Command::List => {
+            for d in DAYS {
+                println!("{} day {}", d.year, d.day);
+            }
+            ExitCode::SUCCESS
+        }
+    }
+}
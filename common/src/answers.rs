@@ -0,0 +1,214 @@
+//! Recorded answers, for checking that solutions still produce them.
+//!
+//! Each day's answers live in `answers.toml` beside its input in the input
+//! cache directory, or beside its solution in this repository if no cache
+//! directory is set, keyed by the SHA-256 of the input they belong to:
+//!
+//! ```toml
+//! year = 2022
+//! day = 16
+//!
+//! [inputs.<sha256 of input>]
+//! part1 = "1986"
+//! part2 = "2464"
+//! ```
+//!
+//! Either part may be left out if its answer isn't known.
+
+use crate::Part;
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/// The name of a day's answers file.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The key an input's answers are recorded under: its SHA-256, in hex.
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// The recorded answers for one input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// How a computed answer compares to the recorded one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL, expected {expected}"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl KnownAnswers {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    pub fn check(&self, part: Part, answer: &str) -> Verdict {
+        match self.get(part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    /// The answers file exists but couldn't be read.
+    Read { path: PathBuf, error: io::Error },
+    /// The answers file couldn't be understood.
+    Malformed { path: PathBuf, reason: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Read { path, error } => {
+                write!(f, "can't read answers file {}: {error}", path.display())
+            }
+            AnswersError::Malformed { path, reason } => {
+                write!(f, "bad answers file {}: {reason}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnswersError::Read { error, .. } => Some(error),
+            AnswersError::Malformed { .. } => None,
+        }
+    }
+}
+
+/// Find the answers recorded for `input` in the contents of an answers file.
+pub fn parse_answers(
+    contents: &str,
+    year: u16,
+    day: u8,
+    input: &str,
+) -> Result<KnownAnswers, String> {
+    let table = contents
+        .parse::<Table>()
+        .map_err(|e| e.message().to_string())?;
+
+    let expect_integer = |key: &str, expected: i64| match table.get(key) {
+        Some(&Value::Integer(n)) if n == expected => Ok(()),
+        Some(value) => Err(format!("{key} is {value}, expected {expected}")),
+        None => Err(format!("missing {key}")),
+    };
+    expect_integer("year", i64::from(year))?;
+    expect_integer("day", i64::from(day))?;
+
+    let Some(inputs) = table.get("inputs") else {
+        return Ok(KnownAnswers::default());
+    };
+    let inputs = inputs.as_table().ok_or("inputs must be a table")?;
+
+    let hash = input_hash(input);
+    let Some(answers) = inputs.get(&hash) else {
+        return Ok(KnownAnswers::default());
+    };
+    let answers = answers
+        .as_table()
+        .ok_or_else(|| format!("inputs.{hash} must be a table"))?;
+
+    let answer = |key: &str| match answers.get(key) {
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(value) => Err(format!("inputs.{hash}.{key} is {value}, expected a string")),
+        None => Ok(None),
+    };
+    Ok(KnownAnswers {
+        part1: answer("part1")?,
+        part2: answer("part2")?,
+    })
+}
+
+/// Load the answers recorded for `input` from the day's answers file in the
+/// input cache directory, or in the day's directory in this repository if
+/// no cache directory is set.  Nothing is known if there's no such file.
+pub fn load_answers(year: u16, day: u8, input: &str) -> Result<KnownAnswers, AnswersError> {
+    let path = crate::cache_path(year, day, ANSWERS_FILE)
+        .unwrap_or_else(|| crate::input::repo_path(year, day, ANSWERS_FILE));
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(KnownAnswers::default()),
+        Err(error) => return Err(AnswersError::Read { path, error }),
+    };
+
+    parse_answers(&contents, year, day, input)
+        .map_err(|reason| AnswersError::Malformed { path, reason })
+}
+
+#[test]
+fn answers_beside_the_solutions() {
+    let path = crate::input::repo_path(2022, 20, ANSWERS_FILE);
+    let contents = fs::read_to_string(path).unwrap();
+    let input = fs::read_to_string(crate::input::repo_path(2022, 20, crate::INPUT_FILE)).unwrap();
+    let known = parse_answers(&contents, 2022, 20, &input).unwrap();
+    assert_eq!(known.check(Part::One, "23321"), Verdict::Pass);
+}
+
+#[test]
+fn hashing() {
+    assert_eq!(
+        input_hash(""),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
+
+#[test]
+fn parsing() {
+    let input = "1\n2\n3\n";
+    let contents = format!(
+        "year = 2022\nday = 1\n\n[inputs.{hash}]\npart1 = \"6\"\n\n[inputs.abc]\npart1 = \"7\"\npart2 = \"8\"\n",
+        hash = input_hash(input)
+    );
+
+    let known = parse_answers(&contents, 2022, 1, input).unwrap();
+    assert_eq!(known.check(Part::One, "6"), Verdict::Pass);
+    assert_eq!(
+        known.check(Part::One, "7"),
+        Verdict::Fail {
+            expected: "6".to_string()
+        }
+    );
+    assert_eq!(known.check(Part::Two, "8"), Verdict::Unknown);
+
+    let unknown = parse_answers(&contents, 2022, 1, "4\n").unwrap();
+    assert_eq!(unknown, KnownAnswers::default());
+
+    assert!(parse_answers(&contents, 2022, 2, input).is_err());
+    assert!(parse_answers(&contents, 2023, 1, input).is_err());
+    assert!(parse_answers("day = 1", 2022, 1, input).is_err());
+    assert!(parse_answers("year = 2022\nday = 1\ninputs = 5", 2022, 1, input).is_err());
+}
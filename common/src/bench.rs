@@ -0,0 +1,357 @@
+//! Timing solutions, and keeping a history of timings to catch regressions.
+//!
+//! A benchmark parses a day's input and solves each part several times,
+//! keeping the fastest time for each phase, since anything slower than that
+//! is noise from elsewhere on the machine.  Each benchmark run is appended
+//! to a JSON history file:
+//!
+//! ```json
+//! {
+//!   "runs": [
+//!     {
+//!       "timestamp": 1700000000,
+//!       "timings": [
+//!         { "year": 2022, "day": 16, "phase": "parse", "nanos": 41000 },
+//!         { "year": 2022, "day": 16, "phase": "part1", "nanos": 9000000 }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! A timing regresses if it's slower than the most recent earlier timing of
+//! the same phase of the same day by more than some threshold.
+
+use crate::{ParseError, Part, Solution, INPUT_DIR_VAR};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The name of the benchmark history file.
+pub const HISTORY_FILE: &str = "bench-history.json";
+
+/// The default history file: in the input cache directory if one is set,
+/// otherwise in the current directory.
+pub fn history_path() -> PathBuf {
+    let mut path = env::var_os(INPUT_DIR_VAR)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    path.push(HISTORY_FILE);
+    path
+}
+
+/// The part of solving a day's puzzle that was timed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl From<Part> for Phase {
+    fn from(part: Part) -> Phase {
+        match part {
+            Part::One => Phase::Part1,
+            Part::Two => Phase::Part2,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "{}", Part::One),
+            Phase::Part2 => write!(f, "{}", Part::Two),
+        }
+    }
+}
+
+/// The fastest time one phase of one day took.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    pub nanos: u64,
+}
+
+impl Timing {
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.nanos)
+    }
+
+    fn same_phase(&self, other: &Timing) -> bool {
+        (self.year, self.day, self.phase) == (other.year, other.day, other.phase)
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {} {}: {:?}",
+            self.year,
+            self.day,
+            self.phase,
+            self.duration()
+        )
+    }
+}
+
+/// Time parsing `input` and solving the given parts, `runs` times over,
+/// keeping the fastest time for each.  A parse error is located within
+/// `input` before being returned.
+pub fn bench<S: Solution>(
+    input: &str,
+    parts: &[Part],
+    runs: u32,
+) -> Result<Vec<Timing>, ParseError> {
+    let mut parse_best = Duration::MAX;
+    let mut part_best = vec![Duration::MAX; parts.len()];
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input)).map_err(|error| error.locate(input))?;
+        parse_best = parse_best.min(start.elapsed());
+
+        for (best, &part) in part_best.iter_mut().zip(parts) {
+            let start = Instant::now();
+            black_box(S::solve(&parsed, part));
+            *best = (*best).min(start.elapsed());
+        }
+    }
+
+    let timing = |phase, duration: Duration| Timing {
+        year: S::YEAR,
+        day: S::DAY,
+        phase,
+        nanos: u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX),
+    };
+
+    let mut timings = vec![timing(Phase::Parse, parse_best)];
+    timings.extend(
+        parts
+            .iter()
+            .zip(part_best)
+            .map(|(&part, best)| timing(part.into(), best)),
+    );
+    Ok(timings)
+}
+
+/// The timings from one benchmark run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// When the run happened, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub timings: Vec<Timing>,
+}
+
+/// A timing that's slower than the one before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub previous: Timing,
+    pub current: Timing,
+}
+
+impl Regression {
+    /// How much slower the current timing is, as a percentage.
+    pub fn slowdown_percent(&self) -> f64 {
+        (self.current.nanos as f64 / self.previous.nanos.max(1) as f64 - 1.0) * 100.0
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, was {:?} (+{:.0}%)",
+            self.current,
+            self.previous.duration(),
+            self.slowdown_percent()
+        )
+    }
+}
+
+/// Every benchmark run so far, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<BenchRun>,
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    /// The history file exists but couldn't be read.
+    Read { path: PathBuf, error: io::Error },
+    /// The history file couldn't be understood.
+    Malformed { path: PathBuf, reason: String },
+    /// The history file couldn't be written.
+    Write { path: PathBuf, error: io::Error },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Read { path, error } => {
+                write!(
+                    f,
+                    "can't read benchmark history {}: {error}",
+                    path.display()
+                )
+            }
+            HistoryError::Malformed { path, reason } => {
+                write!(f, "bad benchmark history {}: {reason}", path.display())
+            }
+            HistoryError::Write { path, error } => {
+                write!(
+                    f,
+                    "can't write benchmark history {}: {error}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl Error for HistoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HistoryError::Read { error, .. } | HistoryError::Write { error, .. } => Some(error),
+            HistoryError::Malformed { .. } => None,
+        }
+    }
+}
+
+impl History {
+    /// Load the history at `path`.  There's no history yet if there's no such
+    /// file.
+    pub fn load(path: &Path) -> Result<History, HistoryError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(error) => {
+                return Err(HistoryError::Read {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        };
+
+        serde_json::from_str(&contents).map_err(|error| HistoryError::Malformed {
+            path: path.to_path_buf(),
+            reason: error.to_string(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), HistoryError> {
+        let contents = serde_json::to_string_pretty(self).expect("history serializes");
+        fs::write(path, contents + "\n").map_err(|error| HistoryError::Write {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// The most recent recorded timing of the same phase of the same day.
+    pub fn previous(&self, timing: &Timing) -> Option<&Timing> {
+        self.runs
+            .iter()
+            .rev()
+            .find_map(|run| run.timings.iter().find(|t| t.same_phase(timing)))
+    }
+
+    /// The timings in `run` that are more than `threshold_percent` slower than
+    /// their previous timings.
+    pub fn regressions(&self, run: &BenchRun, threshold_percent: u32) -> Vec<Regression> {
+        run.timings
+            .iter()
+            .filter_map(|current| {
+                let previous = self.previous(current)?;
+                let regression = Regression {
+                    previous: previous.clone(),
+                    current: current.clone(),
+                };
+                (regression.slowdown_percent() > f64::from(threshold_percent)).then_some(regression)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+fn timing(day: u8, phase: Phase, nanos: u64) -> Timing {
+    Timing {
+        year: 2022,
+        day,
+        phase,
+        nanos,
+    }
+}
+
+#[test]
+fn regressions() {
+    let history = History {
+        runs: vec![
+            BenchRun {
+                timestamp: 1,
+                timings: vec![timing(1, Phase::Parse, 100), timing(1, Phase::Part1, 1000)],
+            },
+            BenchRun {
+                timestamp: 2,
+                timings: vec![timing(1, Phase::Part1, 2000), timing(2, Phase::Part2, 500)],
+            },
+        ],
+    };
+
+    assert_eq!(
+        history.previous(&timing(1, Phase::Part1, 0)),
+        Some(&timing(1, Phase::Part1, 2000))
+    );
+    assert_eq!(history.previous(&timing(1, Phase::Part2, 0)), None);
+
+    let run = BenchRun {
+        timestamp: 3,
+        timings: vec![
+            timing(1, Phase::Parse, 109),
+            timing(1, Phase::Part1, 2300),
+            timing(2, Phase::Part2, 400),
+            timing(3, Phase::Parse, 1),
+        ],
+    };
+    let regressions = history.regressions(&run, 10);
+    assert_eq!(
+        regressions,
+        vec![Regression {
+            previous: timing(1, Phase::Part1, 2000),
+            current: timing(1, Phase::Part1, 2300),
+        }]
+    );
+    assert_eq!(
+        regressions[0].to_string(),
+        "2022 day 1 Part 1: 2.3µs, was 2µs (+15%)"
+    );
+    assert!(history.regressions(&run, 20).is_empty());
+}
+
+#[test]
+fn serialization() {
+    let history = History {
+        runs: vec![BenchRun {
+            timestamp: 1700000000,
+            timings: vec![timing(16, Phase::Parse, 41000)],
+        }],
+    };
+
+    let json = serde_json::to_string(&history).unwrap();
+    assert_eq!(
+        json,
+        r#"{"runs":[{"timestamp":1700000000,"timings":[{"year":2022,"day":16,"phase":"parse","nanos":41000}]}]}"#
+    );
+    assert_eq!(serde_json::from_str::<History>(&json).unwrap(), history);
+}
@@ -0,0 +1,203 @@
+//! Finding where a simulation starts repeating itself, so as to skip ahead
+//! to far later steps.
+//!
+//! A simulation's states, step 0 (the starting state) onward, eventually
+//! repeat if there are only finitely many of them.  Once a state recurs,
+//! every later state recurs the same number of steps after it: the states
+//! run into a cycle.  `floyd` and `brent` find that cycle by stepping
+//! copies of the state along, comparing whole states, so they need only
+//! constant memory.  Where the state is too big or costly to compare or
+//! copy, `Fingerprints` instead remembers a fingerprint of each step's
+//! state, which need only capture what determines the states that follow.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states: from step `start` on, the state at each
+/// step is the same as the state `length` steps later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step in the sequence with the same state as step `n`, and
+    /// how many times around the cycle `n` is past it.
+    pub fn reduce(&self, n: u64) -> (usize, u64) {
+        let (start, length) = (self.start as u64, self.length as u64);
+        if n < start {
+            return (n as usize, 0);
+        }
+        let (cycles, offset) = ((n - start) / length, (n - start) % length);
+        ((start + offset) as usize, cycles)
+    }
+
+    /// How much a measurement of the state changes each time around the
+    /// cycle, given `measurements` of steps 0 through `start + length`.
+    pub fn delta(&self, measurements: &[i64]) -> i64 {
+        measurements[self.start + self.length] - measurements[self.start]
+    }
+
+    /// The measurement at step `n`, given `measurements` of steps 0 through
+    /// `start + length`, for a measurement that changes by the same amount
+    /// each time around the cycle, like the height of a growing tower.
+    pub fn extrapolate(&self, measurements: &[i64], n: u64) -> i64 {
+        let (step, cycles) = self.reduce(n);
+        measurements[step] + cycles as i64 * self.delta(measurements)
+    }
+}
+
+/// Find the cycle in the states `x0`, `f(x0)`, `f(f(x0))`, and so on, by
+/// Floyd's "tortoise and hare" algorithm.
+pub fn floyd<S: PartialEq>(x0: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    // Find a step i, a multiple of the cycle length, where x(i) == x(2i).
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let halfway = f(&hare);
+        hare = f(&halfway);
+    }
+
+    // x(start) == x(start + i), so step from 0 and i together to the start.
+    let mut start = 0;
+    let mut tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Find the cycle in the states `x0`, `f(x0)`, `f(f(x0))`, and so on, by
+/// Brent's algorithm, which usually calls `f` fewer times than `floyd`.
+pub fn brent<S: PartialEq + Clone>(x0: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    // Find the cycle length by comparing against the state at each power of
+    // two in turn.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // Step from 0 and `length` together to the start.
+    let mut start = 0;
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// The fingerprints of a simulation's states so far, for finding when one
+/// repeats.
+#[derive(Debug)]
+pub struct Fingerprints<K> {
+    seen: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> Fingerprints<K> {
+    pub fn new() -> Fingerprints<K> {
+        Fingerprints {
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Note the fingerprint of the next step's state, starting with step 0,
+    /// and return the cycle if it's the same as an earlier step's.
+    pub fn record(&mut self, fingerprint: K) -> Option<Cycle> {
+        let step = self.seen.len();
+        match self.seen.get(&fingerprint) {
+            Some(&start) => Some(Cycle {
+                start,
+                length: step - start,
+            }),
+            None => {
+                self.seen.insert(fingerprint, step);
+                None
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq> Default for Fingerprints<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn finds_cycles() {
+    // 0, 1, 2, 3, 4, 5, 6, 7, 3, 4, ...
+    let next = |&n: &u32| if n == 7 { 3 } else { n + 1 };
+    let expected = Cycle {
+        start: 3,
+        length: 5,
+    };
+    assert_eq!(floyd(0, next), expected);
+    assert_eq!(brent(0, next), expected);
+
+    let mut fingerprints = Fingerprints::new();
+    let mut n = 0;
+    let cycle = loop {
+        if let Some(cycle) = fingerprints.record(n) {
+            break cycle;
+        }
+        n = next(&n);
+    };
+    assert_eq!(cycle, expected);
+
+    // Straight into the cycle, and a cycle of one.
+    let around = |&n: &u32| (n + 1) % 6;
+    let stuck = |&n: &u32| (n + 1).min(9);
+    for (x0, f, start, length) in [
+        (5, &around as &dyn Fn(&u32) -> u32, 0, 6),
+        (0, &stuck, 9, 1),
+    ] {
+        assert_eq!(floyd(x0, f), Cycle { start, length });
+        assert_eq!(brent(x0, f), Cycle { start, length });
+    }
+}
+
+#[test]
+fn extrapolates() {
+    let cycle = Cycle {
+        start: 3,
+        length: 5,
+    };
+    assert_eq!(cycle.reduce(2), (2, 0));
+    assert_eq!(cycle.reduce(8), (3, 1));
+    assert_eq!(cycle.reduce(1_000_000_000_000), (5, 199_999_999_999));
+
+    // A tower that grows by 1, 1, 2, then 3, 0, 2, 1, 4 over and over.
+    let heights = [0, 1, 2, 4, 7, 7, 9, 10, 14];
+    assert_eq!(cycle.delta(&heights), 10);
+    assert_eq!(cycle.extrapolate(&heights, 2), 2);
+    assert_eq!(cycle.extrapolate(&heights, 12), 20);
+    assert_eq!(cycle.extrapolate(&heights, 13), 24);
+}
@@ -0,0 +1,251 @@
+//! Checking solutions against the examples in a day's `problem.txt`.
+//!
+//! Rather than copying example input into tests, where it can drift from the
+//! puzzle text, each day lists its examples in `examples.toml` by where they
+//! are in `problem.txt`, along with the answers the text states for them:
+//!
+//! ```toml
+//! [[example]]
+//! first = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB"
+//! part1 = "1651"
+//! part2 = "1707"
+//! ```
+//!
+//! An example is the lines of `problem.txt` starting with the first line
+//! equal to `first` and running up to the next blank line, or, for examples
+//! that contain blank lines, up to and including the next line equal to
+//! `last`.  Each answer must be stated somewhere in `problem.txt` after the
+//! example, so that it's the text's answer and not merely ours.  Either
+//! answer may be left out, e.g. if the example only applies to one part or
+//! its answer depends on something other than the input.
+//!
+//! `example_tests!` generates a test that checks a day's examples.
+
+use crate::{Part, Solution};
+use toml::{Table, Value};
+
+/// One example from the puzzle text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// The line of `problem.txt` the example starts on, counting from 1.
+    pub line: usize,
+    /// The example input, with a trailing newline like a real input.
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Whether `answer` appears in `text` on its own, not as part of some longer
+/// word or number.
+fn states(text: &str, answer: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(answer).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + answer.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// Find the examples listed in `manifest` (the contents of `examples.toml`)
+/// in `problem` (the contents of `problem.txt`).
+pub fn find_examples(manifest: &str, problem: &str) -> Result<Vec<Example>, String> {
+    let table = manifest
+        .parse::<Table>()
+        .map_err(|e| e.message().to_string())?;
+    let Some(entries) = table.get("example") else {
+        return Ok(vec![]);
+    };
+    let entries = entries
+        .as_array()
+        .ok_or("example must be an array of tables")?;
+
+    let lines: Vec<&str> = problem.lines().collect();
+
+    let mut examples = vec![];
+    for (n, entry) in entries.iter().enumerate() {
+        let n = n + 1;
+        let entry = entry
+            .as_table()
+            .ok_or_else(|| format!("example {n} must be a table"))?;
+        let string = |key: &str| match entry.get(key) {
+            Some(Value::String(s)) => Ok(Some(s.as_str())),
+            Some(value) => Err(format!("example {n}: {key} is {value}, expected a string")),
+            None => Ok(None),
+        };
+
+        let first = string("first")?.ok_or_else(|| format!("example {n}: missing first"))?;
+        let start = lines
+            .iter()
+            .position(|line| *line == first)
+            .ok_or_else(|| format!("example {n}: no line {first:?} in problem.txt"))?;
+
+        let end = match string("last")? {
+            Some(last) => {
+                start
+                    + lines[start..]
+                        .iter()
+                        .position(|line| *line == last)
+                        .ok_or_else(|| {
+                            format!("example {n}: no line {last:?} after {first:?} in problem.txt")
+                        })?
+                    + 1
+            }
+            None => {
+                start
+                    + lines[start..]
+                        .iter()
+                        .position(|line| line.is_empty())
+                        .unwrap_or(lines.len() - start)
+            }
+        };
+
+        let rest = lines[end..].join("\n");
+        let answer = |key: &str| -> Result<Option<String>, String> {
+            let Some(answer) = string(key)? else {
+                return Ok(None);
+            };
+            if !states(&rest, answer) {
+                return Err(format!(
+                    "example {n}: {key} answer {answer:?} isn't stated in problem.txt"
+                ));
+            }
+            Ok(Some(answer.to_string()))
+        };
+
+        let mut input = lines[start..end].join("\n");
+        input.push('\n');
+        examples.push(Example {
+            line: start + 1,
+            input,
+            part1: answer("part1")?,
+            part2: answer("part2")?,
+        });
+    }
+    Ok(examples)
+}
+
+/// Check a day's solution against the examples listed in `manifest` and
+/// found in `problem`, describing every wrong answer.
+pub fn check_examples<S: Solution>(manifest: &str, problem: &str) -> Result<(), String> {
+    let examples = find_examples(manifest, problem)?;
+    if examples.is_empty() {
+        return Err("no examples listed".to_string());
+    }
+
+    let mut failures = vec![];
+    for example in &examples {
+        let parsed = match S::parse(&example.input) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                let error = error.locate(&example.input);
+                failures.push(format!(
+                    "example at line {}: bad input: {error}",
+                    example.line
+                ));
+                continue;
+            }
+        };
+
+        for part in Part::ALL {
+            let Some(expected) = example.answer(part) else {
+                continue;
+            };
+            let answer = S::solve(&parsed, part);
+            if answer != expected {
+                failures.push(format!(
+                    "example at line {} {part}: got {answer}, expected {expected}",
+                    example.line
+                ));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+/// Generate a test, `problem_examples`, checking `$day` against the examples
+/// listed in `examples.toml` beside the crate's `problem.txt`.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:ty) => {
+        #[test]
+        fn problem_examples() {
+            let result = $crate::check_examples::<$day>(
+                include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples.toml")),
+                include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/problem.txt")),
+            );
+            if let Err(failures) = result {
+                panic!("{failures}");
+            }
+        }
+    };
+}
+
+#[test]
+fn finding() {
+    let problem = "For example:
+
+1 2
+3 4
+
+The answer is 10, or 24 if you multiply.
+
+Or with a blank line:
+
+5
+
+6
+
+Now the answer is 11.
+";
+    let manifest = r#"
+[[example]]
+first = "1 2"
+part1 = "10"
+part2 = "24"
+
+[[example]]
+first = "5"
+last = "6"
+part1 = "11"
+"#;
+
+    let examples = find_examples(manifest, problem).unwrap();
+    assert_eq!(
+        examples,
+        vec![
+            Example {
+                line: 3,
+                input: "1 2\n3 4\n".to_string(),
+                part1: Some("10".to_string()),
+                part2: Some("24".to_string()),
+            },
+            Example {
+                line: 10,
+                input: "5\n\n6\n".to_string(),
+                part1: Some("11".to_string()),
+                part2: None,
+            },
+        ]
+    );
+
+    let unstated = "[[example]]\nfirst = \"1 2\"\npart1 = \"1\"";
+    assert!(find_examples(unstated, problem).is_err());
+    let missing = "[[example]]\nfirst = \"7\"";
+    assert!(find_examples(missing, problem).is_err());
+    let no_last = "[[example]]\nfirst = \"5\"\nlast = \"7\"";
+    assert!(find_examples(no_last, problem).is_err());
+}
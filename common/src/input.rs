@@ -0,0 +1,141 @@
+//! Finding and reading a day's puzzle input at runtime.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// The environment variable naming the input cache directory.  A day's input
+/// is read from `$AOC_INPUT_DIR/<year>/day-<NN>/input`, the same layout this
+/// repository uses.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The name of a day's input file in the input cache directory.
+pub const INPUT_FILE: &str = "input";
+
+/// Where to read a day's input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The file at this path.
+    Path(PathBuf),
+    /// Standard input.
+    Stdin,
+    /// The day's file in the input cache directory.
+    Cache,
+}
+
+impl InputSource {
+    /// Interpret an optional command-line argument: `-` means standard input,
+    /// anything else is a path, and no argument at all means the input cache.
+    pub fn from_arg(arg: Option<&str>) -> InputSource {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::Path(PathBuf::from(path)),
+            None => InputSource::Cache,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The input file couldn't be read.
+    File { path: PathBuf, error: io::Error },
+    /// Standard input couldn't be read.
+    Stdin(io::Error),
+    /// No input was given and there's no input cache directory to look in.
+    NoInput { year: u16, day: u8 },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::File { path, error } => {
+                write!(f, "can't read input file {}: {error}", path.display())
+            }
+            InputError::Stdin(error) => write!(f, "can't read input from stdin: {error}"),
+            InputError::NoInput { year, day } => write!(
+                f,
+                "no input for {year} day {day}: pass an input file (or - for stdin), \
+                 or set {INPUT_DIR_VAR} to an input cache directory"
+            ),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::File { error, .. } | InputError::Stdin(error) => Some(error),
+            InputError::NoInput { .. } => None,
+        }
+    }
+}
+
+/// The path of one of a day's files in the input cache directory, if one is
+/// set.
+pub fn cache_path(year: u16, day: u8, file: &str) -> Option<PathBuf> {
+    let dir = env::var_os(INPUT_DIR_VAR).filter(|dir| !dir.is_empty())?;
+    Some(day_path(PathBuf::from(dir), year, day, file))
+}
+
+/// The path of one of a day's files beside its solution in this repository,
+/// which is laid out like the input cache directory.
+pub(crate) fn repo_path(year: u16, day: u8, file: &str) -> PathBuf {
+    let mut root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    root.pop();
+    day_path(root, year, day, file)
+}
+
+fn day_path(mut path: PathBuf, year: u16, day: u8, file: &str) -> PathBuf {
+    path.push(year.to_string());
+    path.push(format!("day-{day:02}"));
+    path.push(file);
+    path
+}
+
+/// Read a day's input from `source`.
+pub fn load_input(year: u16, day: u8, source: &InputSource) -> Result<String, InputError> {
+    let read_file = |path: PathBuf| match fs::read_to_string(&path) {
+        Ok(input) => Ok(input),
+        Err(error) => Err(InputError::File { path, error }),
+    };
+
+    match source {
+        InputSource::Path(path) => read_file(path.clone()),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(InputError::Stdin)?;
+            Ok(input)
+        }
+        InputSource::Cache => match cache_path(year, day, INPUT_FILE) {
+            Some(path) => read_file(path),
+            None => Err(InputError::NoInput { year, day }),
+        },
+    }
+}
+
+#[test]
+fn sources() {
+    assert_eq!(InputSource::from_arg(None), InputSource::Cache);
+    assert_eq!(InputSource::from_arg(Some("-")), InputSource::Stdin);
+    assert_eq!(
+        InputSource::from_arg(Some("input")),
+        InputSource::Path(PathBuf::from("input"))
+    );
+}
+
+#[test]
+fn missing_file() {
+    let source = InputSource::Path(PathBuf::from("no/such/input"));
+    match load_input(2022, 16, &source) {
+        Err(InputError::File { path, error }) => {
+            assert_eq!(path, PathBuf::from("no/such/input"));
+            assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+}
@@ -0,0 +1,259 @@
+//! Pieces shared by every day's solution and by the `aoc` runner.
+
+mod answers;
+mod bench;
+pub mod cycle;
+mod examples;
+mod input;
+pub mod ocr;
+pub mod parse;
+
+pub use answers::{
+    input_hash, load_answers, parse_answers, AnswersError, KnownAnswers, Verdict, ANSWERS_FILE,
+};
+pub use bench::{
+    bench, history_path, BenchRun, History, HistoryError, Phase, Regression, Timing, HISTORY_FILE,
+};
+pub use examples::{check_examples, find_examples, Example};
+pub use input::{cache_path, load_input, InputError, InputSource, INPUT_DIR_VAR, INPUT_FILE};
+pub use parse::ParseError;
+
+use std::env;
+use std::fmt;
+use std::process::ExitCode;
+
+/// Which half of a day's puzzle is being solved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Part {}", self.number())
+    }
+}
+
+/// A solution to one day's puzzle.
+///
+/// Input is parsed once by `parse`, then each part computes its answer from
+/// the parsed form.  Malformed input is reported by `parse` as an error rather
+/// than a panic.  Answers are returned as strings because not every puzzle
+/// has a numeric answer.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// The parsed form of the input, which may borrow from the input text.
+    type Parsed<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String;
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String;
+
+    fn solve(parsed: &Self::Parsed<'_>, part: Part) -> String {
+        match part {
+            Part::One => Self::part1(parsed),
+            Part::Two => Self::part2(parsed),
+        }
+    }
+}
+
+/// How many answers passed, failed, or couldn't be checked against the
+/// recorded answers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub passed: usize,
+    pub failed: usize,
+    pub unknown: usize,
+}
+
+impl Tally {
+    pub fn record(&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Pass => self.passed += 1,
+            Verdict::Fail { .. } => self.failed += 1,
+            Verdict::Unknown => self.unknown += 1,
+        }
+    }
+
+    pub fn add(&mut self, other: Tally) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.unknown += other.unknown;
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} unknown",
+            self.passed, self.failed, self.unknown
+        )
+    }
+}
+
+/// Parse `input`, solve the given parts, and print the answers.  If `known`
+/// answers are given, check each answer against them, print the verdict
+/// beside it, and count the verdicts.  A parse error is located within
+/// `input` before being returned.
+pub fn run<S: Solution>(
+    input: &str,
+    parts: &[Part],
+    known: Option<&KnownAnswers>,
+) -> Result<Tally, ParseError> {
+    let parsed = S::parse(input).map_err(|error| error.locate(input))?;
+
+    let mut tally = Tally::default();
+    for &part in parts {
+        let answer = S::solve(&parsed, part);
+        match known {
+            Some(known) => {
+                let verdict = known.check(part, &answer);
+                println!(
+                    "{year} day {day} {part}: {answer} [{verdict}]",
+                    year = S::YEAR,
+                    day = S::DAY
+                );
+                tally.record(&verdict);
+            }
+            None => println!(
+                "{year} day {day} {part}: {answer}",
+                year = S::YEAR,
+                day = S::DAY
+            ),
+        }
+    }
+    Ok(tally)
+}
+
+const USAGE: &str = "usage: <day> [--verify] [<input file>|-]";
+
+/// A day-specific way to run a solution instead of printing its answers,
+/// such as drawing a picture of how it works.  A mode is chosen by passing
+/// its flag on the command line, followed by an argument for each of its
+/// `params`; it runs on the parsed input and those arguments and reports
+/// failure as a message.
+pub struct Mode<S: Solution> {
+    pub flag: &'static str,
+    /// What each of the mode's arguments is, for the usage message.
+    pub params: &'static [&'static str],
+    pub run: fn(&S::Parsed<'_>, &[String]) -> Result<(), String>,
+}
+
+/// Run a day's solution as directed by the command line: `--verify` to check
+/// answers against the recorded ones, then a path to the input, `-` for
+/// standard input, or nothing to use the input cache.
+pub fn main<S: Solution>() -> ExitCode {
+    main_with_modes::<S>(&[])
+}
+
+/// Like `main`, except that a flag for one of `modes` may be given instead of
+/// `--verify` to run that mode.
+pub fn main_with_modes<S: Solution>(modes: &[Mode<S>]) -> ExitCode {
+    let usage = || {
+        eprintln!("{USAGE}");
+        for mode in modes {
+            let params = mode.params.iter().map(|param| format!(" <{param}>"));
+            eprintln!(
+                "       <day> {}{} [<input file>|-]",
+                mode.flag,
+                params.collect::<String>()
+            );
+        }
+    };
+
+    let mut verify = false;
+    let mut mode = None;
+    let mut mode_args = vec![];
+    let mut input = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let chosen = modes.iter().find(|m| m.flag == arg);
+        if (arg == "--verify" || chosen.is_some()) && (verify || mode.is_some()) {
+            eprintln!("only one of --verify and a mode may be given");
+            usage();
+            return ExitCode::from(2);
+        }
+
+        if arg == "--verify" {
+            verify = true;
+        } else if let Some(chosen) = chosen {
+            mode_args = args.by_ref().take(chosen.params.len()).collect();
+            if mode_args.len() < chosen.params.len() {
+                eprintln!("{arg} needs <{}>", chosen.params[mode_args.len()]);
+                usage();
+                return ExitCode::from(2);
+            }
+            mode = Some(chosen);
+        } else if input.is_none() {
+            input = Some(arg);
+        } else {
+            eprintln!("unexpected argument: {arg:?}");
+            usage();
+            return ExitCode::from(2);
+        }
+    }
+
+    let source = InputSource::from_arg(input.as_deref());
+    let input = match load_input(S::YEAR, S::DAY, &source) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(mode) = mode {
+        let parsed = match S::parse(&input) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                eprintln!("bad input: {}", error.locate(&input));
+                return ExitCode::FAILURE;
+            }
+        };
+        return match (mode.run)(&parsed, &mode_args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let known = if verify {
+        match load_answers(S::YEAR, S::DAY, &input) {
+            Ok(known) => Some(known),
+            Err(error) => {
+                eprintln!("{error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+
+    match run::<S>(&input, &Part::ALL, known.as_ref()) {
+        Ok(tally) if tally.failed > 0 => ExitCode::FAILURE,
+        Ok(_) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("bad input: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Reading the capital letters some puzzles draw with `#` for a lit pixel,
+//! in either of the two fonts the puzzles use: letters six pixels tall and
+//! (mostly) four wide, or letters ten pixels tall and six wide.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+type Glyph = (char, &'static [&'static str]);
+
+const FONT_6: &[Glyph] = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const FONT_10: &[Glyph] = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+            "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######",
+            "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.",
+            "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+            "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+            "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+            "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..",
+            "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The letters aren't as tall as either font's.
+    Height(usize),
+    /// The glyphs starting at these columns aren't letters in the font.
+    Unrecognized(Vec<usize>),
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height(height) => {
+                write!(f, "letters {height} pixels tall aren't in any known font")
+            }
+            OcrError::Unrecognized(columns) => {
+                let columns = columns.iter().map(usize::to_string).collect::<Vec<_>>();
+                write!(f, "unrecognized glyphs at columns {}", columns.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// Read the letters in `image`, lines of `#` for lit pixels and anything
+/// else for unlit ones.  Letters are read from cells of fixed width from the
+/// left edge, as the puzzles draw them (five columns for the shorter font,
+/// eight for the taller), or failing that, from runs of columns separated by
+/// unlit columns.  Blank lines before and after the letters are ignored.
+pub fn read(image: &str) -> Result<String, OcrError> {
+    let rows = image
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let lit_rows = rows
+        .iter()
+        .position(|row| row.contains(&true))
+        .map_or(0..0, |first| {
+            let last = rows.iter().rposition(|row| row.contains(&true)).unwrap();
+            first..last + 1
+        });
+    let rows = &rows[lit_rows];
+
+    let (font, cell_width) = match rows.len() {
+        6 => (FONT_6, 5),
+        10 => (FONT_10, 8),
+        height => return Err(OcrError::Height(height)),
+    };
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |row: &Vec<bool>, column: usize| row.get(column) == Some(&true);
+    let blank = |column: usize| !rows.iter().any(|row| lit(row, column));
+
+    // The letters in each of `glyphs`, column ranges trimmed of unlit
+    // columns, and where the unrecognized ones start.
+    let recognize = |glyphs: Vec<Range<usize>>| {
+        let mut letters = String::new();
+        let mut unrecognized = vec![];
+        for glyph in glyphs {
+            let Some(start) = glyph.clone().find(|&c| !blank(c)) else {
+                continue;
+            };
+            let end = glyph.clone().rfind(|&c| !blank(c)).unwrap() + 1;
+            let pixels = rows
+                .iter()
+                .map(|row| {
+                    (start..end)
+                        .map(|c| if lit(row, c) { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+
+            match font.iter().find(|(_, glyph)| *glyph == pixels) {
+                Some(&(letter, _)) => letters.push(letter),
+                None => unrecognized.push(start),
+            }
+        }
+        (letters, unrecognized)
+    };
+
+    let cells = (0..width)
+        .step_by(cell_width)
+        .map(|start| start..(start + cell_width).min(width))
+        .collect();
+    let (letters, in_cells) = recognize(cells);
+    if in_cells.is_empty() {
+        return Ok(letters);
+    }
+
+    let mut runs = vec![];
+    let mut column = 0;
+    while column < width {
+        if blank(column) {
+            column += 1;
+            continue;
+        }
+        let start = column;
+        while column < width && !blank(column) {
+            column += 1;
+        }
+        runs.push(start..column);
+    }
+    let (letters, in_runs) = recognize(runs);
+    if in_runs.is_empty() {
+        return Ok(letters);
+    }
+
+    Err(OcrError::Unrecognized(if in_runs.len() < in_cells.len() {
+        in_runs
+    } else {
+        in_cells
+    }))
+}
+
+#[cfg(test)]
+fn draw(font: &[Glyph], text: &str) -> String {
+    let height = font[0].1.len();
+    let glyphs = text
+        .chars()
+        .map(|c| font.iter().find(|(letter, _)| *letter == c).unwrap().1)
+        .collect::<Vec<_>>();
+    (0..height)
+        .map(|row| {
+            let row = glyphs.iter().map(|glyph| glyph[row]).collect::<Vec<_>>();
+            row.join(".") + "\n"
+        })
+        .collect()
+}
+
+#[test]
+fn reads_both_fonts() {
+    let image = "\
+####.####..##..####.###..#..#.###..####.
+#....#....#..#.#....#..#.#..#.#..#.#....
+###..###..#....###..#..#.#..#.#..#.###..
+#....#....#.##.#....###..#..#.###..#....
+#....#....#..#.#....#.#..#..#.#.#..#....
+####.#.....###.####.#..#..##..#..#.####.
+";
+    assert_eq!(read(image), Ok("EFGERURE".to_string()));
+    assert_eq!(read(&format!("\n{image}\n")), Ok("EFGERURE".to_string()));
+
+    for (font, letters) in [(FONT_6, "ABCEFGHIJKLOPRSUYZ"), (FONT_10, "ABCEFGHJKLNPRXZ")] {
+        assert_eq!(read(&draw(font, letters)), Ok(letters.to_string()));
+    }
+}
+
+#[test]
+fn reads_letters_in_cells() {
+    // Each letter at the left of a cell of fixed width, so that a letter as
+    // wide as its cell abuts the next.
+    fn draw_in_cells(font: &[Glyph], text: &str, cell_width: usize) -> String {
+        let height = font[0].1.len();
+        let glyphs = text
+            .chars()
+            .map(|c| font.iter().find(|(letter, _)| *letter == c).unwrap().1)
+            .collect::<Vec<_>>();
+        (0..height)
+            .map(|row| {
+                let cells = glyphs
+                    .iter()
+                    .map(|glyph| format!("{:.<cell_width$}", glyph[row]))
+                    .collect::<String>();
+                cells + "\n"
+            })
+            .collect()
+    }
+
+    let image = draw_in_cells(FONT_6, "YEYZ", 5);
+    assert!(image.starts_with("#...#####.#...#####"));
+    assert_eq!(read(&image), Ok("YEYZ".to_string()));
+
+    for (font, letters, cell_width) in [
+        (FONT_6, "ABCEFGHIJKLOPRSUYZ", 5),
+        (FONT_10, "ABCEFGHJKLNPRXZ", 8),
+    ] {
+        let image = draw_in_cells(font, letters, cell_width);
+        assert_eq!(read(&image), Ok(letters.to_string()));
+    }
+}
+
+#[test]
+fn reports_unrecognized_glyphs() {
+    let mut rows = draw(FONT_6, "HELLO")
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    rows[0][1] = '#';
+    rows[2][21] = '#';
+    let image = rows
+        .iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect::<String>();
+    assert_eq!(read(&image), Err(OcrError::Unrecognized(vec![0, 20])));
+    assert_eq!(
+        read(&image).unwrap_err().to_string(),
+        "unrecognized glyphs at columns 0, 20"
+    );
+
+    assert_eq!(read("#\n#\n#"), Err(OcrError::Height(3)));
+    assert_eq!(read(""), Err(OcrError::Height(0)));
+}
@@ -0,0 +1,159 @@
+//! Errors in puzzle input, and small helpers for reporting them.
+//!
+//! Parsers report an error by pointing at the offending text, which is
+//! usually a slice of the input they were given.  The error is located by
+//! line and column once the whole input is at hand, by `ParseError::locate`,
+//! so parsers that only see one line or one block of the input needn't track
+//! where it came from.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based line and column (in characters) of the offending text, if
+    /// it's been located in the input.
+    pub location: Option<(usize, usize)>,
+    /// The offending text.
+    pub text: String,
+    /// A description of what was expected instead.
+    pub expected: String,
+    /// The addresses `text` occupied when the error was created, for locating
+    /// it within the input.
+    span: Range<usize>,
+}
+
+impl ParseError {
+    /// An error at `text`, which wasn't the `expected` thing.  If `text` is a
+    /// slice of the input, `locate` can find its line and column.
+    pub fn new(text: &str, expected: impl Into<String>) -> ParseError {
+        let start = text.as_ptr() as usize;
+        ParseError {
+            location: None,
+            text: text.to_string(),
+            expected: expected.into(),
+            span: start..start + text.len(),
+        }
+    }
+
+    /// An error at the end of `text`, which stopped short of the `expected`
+    /// thing.
+    pub fn after(text: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(&text[text.len()..], expected)
+    }
+
+    /// Find the line and column of the offending text in `input`, if it's
+    /// a slice of it.
+    pub fn locate(mut self, input: &str) -> ParseError {
+        if self.location.is_some() {
+            return self;
+        }
+
+        let base = input.as_ptr() as usize;
+        if self.span.start < base || self.span.end > base + input.len() {
+            return self;
+        }
+
+        let before = &input[..self.span.start - base];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        self.location = Some((line, column));
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((line, column)) = self.location {
+            write!(f, "line {line}, column {column}: ")?;
+        }
+
+        // Only the first line of a long stretch of text is useful.
+        let text = match self.text.split_once('\n') {
+            Some((first, _)) => format!("{first}..."),
+            None => self.text.clone(),
+        };
+        write!(f, "expected {}, found {text:?}", self.expected)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse `text` as a `T`, or report that it isn't the `expected` thing.
+pub fn value<T: FromStr>(text: &str, expected: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|_| ParseError::new(text, expected))
+}
+
+/// Split `text` around the first `delimiter`.
+pub fn split_once<'a>(text: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(text, format!("{delimiter:?}")))
+}
+
+/// Remove `prefix` from the start of `text`.
+pub fn strip_prefix<'a>(text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    text.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(text, format!("{prefix:?}")))
+}
+
+/// Remove `suffix` from the end of `text`.
+pub fn strip_suffix<'a>(text: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    text.strip_suffix(suffix)
+        .ok_or_else(|| ParseError::new(&text[text.len()..], format!("{suffix:?}")))
+}
+
+/// The next item from `iter`, where `text` is what's being split into items
+/// and ran out before the `expected` item.
+pub fn next<'a>(
+    iter: &mut impl Iterator<Item = &'a str>,
+    text: &str,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    iter.next().ok_or_else(|| ParseError::after(text, expected))
+}
+
+#[test]
+fn locating() {
+    let input = "1 2\n3 x 4\n";
+    let line = input.lines().nth(1).unwrap();
+    let word = line.split(' ').nth(1).unwrap();
+
+    let error = value::<u32>(word, "a number").unwrap_err().locate(input);
+    assert_eq!(error.location, Some((2, 3)));
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 3: expected a number, found \"x\""
+    );
+
+    let error = ParseError::after(input.trim_end(), "more").locate(input);
+    assert_eq!(error.location, Some((2, 6)));
+
+    let elsewhere = String::from("x");
+    let error = ParseError::new(&elsewhere, "a number").locate(input);
+    assert_eq!(error.location, None);
+    assert_eq!(error.to_string(), "expected a number, found \"x\"");
+}
+
+#[test]
+fn helpers() {
+    let input = "Monkey 3: a -> b";
+    assert_eq!(strip_prefix(input, "Monkey "), Ok("3: a -> b"));
+    assert_eq!(split_once(input, " -> "), Ok(("Monkey 3: a", "b")));
+    assert_eq!(strip_suffix(input, "b"), Ok("Monkey 3: a -> "));
+
+    let error = split_once(input, " <- ").unwrap_err().locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 1, column 1: expected \" <- \", found \"Monkey 3: a -> b\""
+    );
+
+    let error = strip_suffix(input, ":").unwrap_err().locate(input);
+    assert_eq!(error.location, Some((1, 17)));
+
+    let mut words = input.split(' ');
+    assert_eq!(next(&mut words, input, "a word"), Ok("Monkey"));
+    assert!(next(&mut words.by_ref().skip(9), input, "a word").is_err());
+}
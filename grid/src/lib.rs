@@ -0,0 +1,532 @@
+//! A rectangular grid of cells, the shape so many puzzle inputs take.
+//!
+//! Positions are `(x, y)` pairs: `x` counts columns rightward from the left
+//! edge, and `y` counts rows downward from the top edge.
+
+use aoc_common::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A position within a grid, as `(x, y)`.
+pub type Pos = (usize, usize);
+
+/// A step from one position to another, as `(dx, dy)`.
+pub type Offset = (isize, isize);
+
+pub const UP: Offset = (0, -1);
+pub const UP_RIGHT: Offset = (1, -1);
+pub const RIGHT: Offset = (1, 0);
+pub const DOWN_RIGHT: Offset = (1, 1);
+pub const DOWN: Offset = (0, 1);
+pub const DOWN_LEFT: Offset = (-1, 1);
+pub const LEFT: Offset = (-1, 0);
+pub const UP_LEFT: Offset = (-1, -1);
+
+/// The four orthogonal steps, clockwise from up.
+pub const ORTHOGONAL: [Offset; 4] = [UP, RIGHT, DOWN, LEFT];
+
+/// All eight steps to an adjacent position, clockwise from up.
+pub const ALL_DIRECTIONS: [Offset; 8] = [
+    UP, UP_RIGHT, RIGHT, DOWN_RIGHT, DOWN, DOWN_LEFT, LEFT, UP_LEFT,
+];
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parse a character map whose lines may differ in length, padding short
+    /// lines out to the longest with `fill`.  Each character is converted
+    /// with `f`, which returns `None` if the character isn't the `expected`
+    /// sort.
+    pub fn parse_ragged(
+        s: &str,
+        fill: T,
+        expected: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let width = s
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut cells = vec![];
+        for line in s.lines() {
+            let start = cells.len();
+            parse_line(line, expected, &mut f, &mut cells)?;
+            cells.resize(start + width, fill.clone());
+        }
+
+        Ok(Grid::from_vec(width, cells))
+    }
+
+    /// The grid flipped along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid::from_vec(self.height, cells)
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Grid::from_vec(self.height, cells)
+    }
+
+    /// The grid turned a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid::from_vec(self.height, cells)
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given width from its cells in row-major order.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid<T> {
+        let height = if width == 0 {
+            assert!(cells.is_empty(), "cells in a grid of no width");
+            0
+        } else {
+            assert!(
+                cells.len().is_multiple_of(width),
+                "{} cells don't fill rows of width {width}",
+                cells.len()
+            );
+            cells.len() / width
+        };
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// A grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in rows {
+            assert_eq!(row.len(), width, "all rows must be the same length");
+            cells.extend(row);
+        }
+        Grid::from_vec(width, cells)
+    }
+
+    /// Parse a character map, one row per line, converting each character
+    /// with `f`, which returns `None` if the character isn't the `expected`
+    /// sort.  Every line must be the same length.
+    pub fn parse(
+        s: &str,
+        expected: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut cells = vec![];
+        for line in s.lines() {
+            let len = line.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(ParseError::new(
+                        line,
+                        format!("a row of {width} characters"),
+                    ));
+                }
+                Some(_) => {}
+            }
+
+            parse_line(line, expected, &mut f, &mut cells)?;
+        }
+
+        Ok(Grid::from_vec(width.unwrap_or(0), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn index_of(&self, (x, y): Pos) -> usize {
+        assert!(x < self.width, "x out of bounds: {x} >= {}", self.width);
+        assert!(y < self.height, "y out of bounds: {y} >= {}", self.height);
+        y * self.width + x
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.in_bounds(pos) {
+            Some(&self.cells[self.index_of(pos)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            let index = self.index_of(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// The position one `offset` step from `pos`, if it's within the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): Offset) -> Option<Pos> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        Some((x, y)).filter(|&pos| self.in_bounds(pos))
+    }
+
+    /// The position one `offset` step from `pos`, wrapping around to the
+    /// opposite edge on leaving the grid.
+    pub fn offset_wrapping(&self, (x, y): Pos, (dx, dy): Offset) -> Pos {
+        assert!(self.in_bounds((x, y)), "position out of bounds");
+        let wrap = |coord: usize, delta: isize, limit: usize| {
+            (coord as isize + delta).rem_euclid(limit as isize) as usize
+        };
+        (wrap(x, dx, self.width), wrap(y, dy, self.height))
+    }
+
+    /// The orthogonally adjacent positions within the grid, clockwise from
+    /// up.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// The orthogonally and diagonally adjacent positions within the grid,
+    /// clockwise from up.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// The orthogonally adjacent positions, wrapping around the edges.  In a
+    /// grid narrower or shorter than three cells, positions may repeat.
+    pub fn neighbors4_wrapping(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .iter()
+            .map(move |&d| self.offset_wrapping(pos, d))
+    }
+
+    /// The orthogonally and diagonally adjacent positions, wrapping around
+    /// the edges.  In a grid narrower or shorter than three cells, positions
+    /// may repeat.
+    pub fn neighbors8_wrapping(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .map(move |&d| self.offset_wrapping(pos, d))
+    }
+
+    /// The positions reached by repeatedly stepping `offset` from `pos`
+    /// (not including `pos` itself), until the edge of the grid.
+    pub fn ray(&self, pos: Pos, offset: Offset) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.offset(pos, offset), move |&pos| {
+            self.offset(pos, offset)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        let start = self.index_of((0, y));
+        &self.cells[start..start + self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        let start = self.index_of((0, y));
+        &mut self.cells[start..start + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` won't take zero, but then there are no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "x out of bounds: {x} >= {}", self.width);
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells running down and to the right from `start`, inclusive.
+    pub fn diagonal(&self, start: Pos) -> impl Iterator<Item = &T> {
+        std::iter::once(start)
+            .chain(self.ray(start, DOWN_RIGHT))
+            .map(|pos| &self[pos])
+    }
+
+    /// The cells running down and to the left from `start`, inclusive.
+    pub fn anti_diagonal(&self, start: Pos) -> impl Iterator<Item = &T> {
+        std::iter::once(start)
+            .chain(self.ray(start, DOWN_LEFT))
+            .map(|pos| &self[pos])
+    }
+
+    /// Every down-and-right diagonal, from the one starting in the bottom
+    /// left corner to the one starting in the top right corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let left_edge = (0..self.height).rev().map(|y| (0, y));
+        let top_edge = (1..self.width).map(|x| (x, 0));
+        left_edge.chain(top_edge).map(|start| self.diagonal(start))
+    }
+
+    /// Every down-and-left diagonal, from the one starting in the top left
+    /// corner to the one starting in the bottom right corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top_edge = (0..self.width).map(|x| (x, 0));
+        let right_edge = (1..self.height).map(|y| (self.width - 1, y));
+        top_edge
+            .chain(right_edge)
+            .map(|start| self.anti_diagonal(start))
+    }
+
+    /// Every position, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell, in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every cell with its position, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, in row-major order, satisfying
+    /// `pred`.
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        let index = self.cells.iter().position(pred)?;
+        Some((index % self.width, index / self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Draw the grid with one character per cell and one line per row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+fn parse_line<T>(
+    line: &str,
+    expected: &str,
+    f: &mut impl FnMut(char) -> Option<T>,
+    cells: &mut Vec<T>,
+) -> Result<(), ParseError> {
+    for (i, c) in line.char_indices() {
+        let cell = f(c).ok_or_else(|| ParseError::new(&line[i..i + c.len_utf8()], expected))?;
+        cells.push(cell);
+    }
+    Ok(())
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let index = self.index_of(pos);
+        &mut self.cells[index]
+    }
+}
+
+/// Each row on its own line, with cells written one after another.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+#[cfg(test)]
+static EXAMPLE: &str = "abc
+def
+";
+
+#[test]
+fn parsing() {
+    let grid = Grid::parse(EXAMPLE, "a letter", Some).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(1, 0)], 'b');
+    assert_eq!(grid[(2, 1)], 'f');
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.to_string(), EXAMPLE);
+    assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+
+    let ragged = Grid::parse_ragged("  #\n.\n", ' ', "a tile", Some).unwrap();
+    assert_eq!(ragged.render(|&c| c), "  #\n.  \n");
+
+    let empty = Grid::parse("", "a letter", Some).unwrap();
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+    assert_eq!(empty.rows().count(), 0);
+}
+
+#[test]
+fn parse_errors() {
+    let input = "ab\nc\n";
+    let error = Grid::parse(input, "a letter", Some)
+        .unwrap_err()
+        .locate(input);
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 1: expected a row of 2 characters, found \"c\""
+    );
+
+    let input = "ab\ncd\nex\n";
+    let error = Grid::parse(input, "a vowel", |c| "aeiou".contains(c).then_some(c))
+        .unwrap_err()
+        .locate(input);
+    assert_eq!(error.location, Some((1, 2)));
+    assert_eq!(error.text, "b");
+}
+
+#[test]
+fn neighbors() {
+    let grid = Grid::new(3, 3, ());
+
+    assert_eq!(
+        grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+        vec![(1, 0), (0, 1)]
+    );
+    assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+    assert_eq!(
+        grid.neighbors8((2, 0)).collect::<Vec<_>>(),
+        vec![(2, 1), (1, 1), (1, 0)]
+    );
+    assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+
+    assert_eq!(
+        grid.neighbors4_wrapping((0, 0)).collect::<Vec<_>>(),
+        vec![(0, 2), (1, 0), (0, 1), (2, 0)]
+    );
+    assert_eq!(
+        grid.neighbors8_wrapping((2, 2)).collect::<Vec<_>>(),
+        vec![
+            (2, 1),
+            (0, 1),
+            (0, 2),
+            (0, 0),
+            (2, 0),
+            (1, 0),
+            (1, 2),
+            (1, 1)
+        ]
+    );
+
+    assert_eq!(
+        grid.ray((0, 1), RIGHT).collect::<Vec<_>>(),
+        vec![(1, 1), (2, 1)]
+    );
+    assert_eq!(grid.ray((0, 1), LEFT).count(), 0);
+}
+
+#[test]
+fn lines() {
+    let grid = Grid::parse(EXAMPLE, "a letter", Some).unwrap();
+
+    assert_eq!(grid.row(1), ['d', 'e', 'f']);
+    assert_eq!(
+        grid.rows().collect::<Vec<_>>(),
+        vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+    );
+    assert_eq!(grid.column(2).collect::<String>(), "cf");
+    assert_eq!(
+        grid.columns()
+            .map(|column| column.collect::<String>())
+            .collect::<Vec<_>>(),
+        ["ad", "be", "cf"]
+    );
+    assert_eq!(
+        grid.diagonals()
+            .map(|diagonal| diagonal.collect::<String>())
+            .collect::<Vec<_>>(),
+        ["d", "ae", "bf", "c"]
+    );
+    assert_eq!(
+        grid.anti_diagonals()
+            .map(|diagonal| diagonal.collect::<String>())
+            .collect::<Vec<_>>(),
+        ["a", "bd", "ce", "f"]
+    );
+}
+
+#[test]
+fn transforms() {
+    let grid = Grid::parse(EXAMPLE, "a letter", Some).unwrap();
+
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+    assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+    assert_eq!(
+        grid.rotate_clockwise().rotate_clockwise().to_string(),
+        "fed\ncba\n"
+    );
+    assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    assert_eq!(
+        grid.map(|c| c.to_ascii_uppercase()).to_string(),
+        "ABC\nDEF\n"
+    );
+}